@@ -1,18 +1,171 @@
 #![no_std]
 
 use soroban_sdk::{
-    contract, contractclient, contractimpl, symbol_short, Address, BytesN, Env, Symbol, Vec,
+    contract, contractclient, contracterror, contractimpl, contracttype, symbol_short,
+    xdr::{ScErrorType, ToXdr},
+    Address, BytesN, Env, Symbol, Vec,
 };
 
 mod test;
 
 const WASM_HASH: Symbol = symbol_short!("wasm");
+const SPLITTER_COUNT: Symbol = symbol_short!("count");
+const ADMIN: Symbol = symbol_short!("admin");
+const LATEST_VERSION: Symbol = symbol_short!("version");
 const ONE_YEAR_LEDGERS: u32 = 5_184_000;
 
+/// Upper bound on the number of entries returned by a single paginated view.
+const MAX_PAGE_SIZE: u32 = 100;
+
+/// Splitters per persistent entry of a recipient's reverse index. Creating a
+/// splitter writes one index entry per recipient, plus one more whenever a
+/// recipient's current page fills up.
+const RECIPIENT_INDEX_PAGE: u32 = 100;
+
+/// Errors returned by SimpleSplitterFactory entrypoints.
+/// Codes are part of the public interface and must not be renumbered.
+///
+/// Codes 6 to 13 pass through the matching SimpleSplitter `init` error.
+/// `InvalidSplitterConfig` covers any other splitter error code, and
+/// `SplitterInitFailed` a failure that is not a contract error at all.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    InvalidSplitterConfig = 3,
+    SaltAlreadyUsed = 4,
+    UnknownVersion = 5,
+    LengthMismatch = 6,
+    ZeroTotalShares = 7,
+    DuplicateRecipient = 8,
+    SelfRecipient = 9,
+    TooManyRecipients = 10,
+    InvalidBasisPoints = 11,
+    InvalidKeeperFee = 12,
+    InvalidMinDistribution = 13,
+    SplitterInitFailed = 14,
+}
+
+/// Persistent storage keys.
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    /// Registry entry for the splitter created at this index.
+    Splitter(u32),
+    /// Reverse index of the splitters paying a recipient: the number of full
+    /// pages stored under `RecipientPage` and the splitters of the current,
+    /// partly filled page.
+    RecipientSplitters(Address),
+    /// A full page of `RECIPIENT_INDEX_PAGE` splitters in a recipient's index.
+    RecipientPage(Address, u32),
+    /// WASM hash registered under this version number.
+    WasmVersion(u32),
+    /// WASM version a deployed splitter was created from. Its presence also
+    /// marks the salt the splitter was deployed at as used.
+    SplitterVersion(Address),
+}
+
+/// A SimpleSplitter WASM hash registered with the factory.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WasmVersion {
+    pub version: u32,
+    pub wasm_hash: BytesN<32>,
+    /// Ledger sequence at which the hash was registered.
+    pub ledger: u32,
+}
+
+/// Registry entry recorded for every splitter the factory deploys.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SplitterInfo {
+    pub address: Address,
+    pub creator: Address,
+    pub token: Address,
+    /// Ledger sequence at which the splitter was created.
+    pub ledger: u32,
+    /// WASM version the splitter was deployed from. Not updated when the
+    /// splitter's owner later calls `upgrade`; ask the splitter's `version()`.
+    pub version: u32,
+}
+
+/// Mirror of SimpleSplitter's `PayoutMode`; variant names must match.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum PayoutMode {
+    #[default]
+    Push,
+    Claim,
+}
+
+/// Mirror of SimpleSplitter's `RemainderPolicy`; variant names must match.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum RemainderPolicy {
+    #[default]
+    Keep,
+    FirstRecipient,
+    LargestShareholder,
+    RoundRobin,
+}
+
+/// Mirror of SimpleSplitter's `ShareMode`; variant names must match.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ShareMode {
+    #[default]
+    Ratio,
+    BasisPoints,
+}
+
+/// Mirror of SimpleSplitter's `StorageLayout`; variant names must match.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum StorageLayout {
+    #[default]
+    Instance,
+    Persistent,
+}
+
+/// Mirror of SimpleSplitter's `KeeperFee`; variant names must match.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum KeeperFee {
+    #[default]
+    None,
+    Fixed(i128),
+    BasisPoints(u32),
+}
+
+/// Mirror of SimpleSplitter's `SplitterOptions`; field names must match.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SplitterOptions {
+    pub owner: Option<Address>,
+    pub guardian: Option<Address>,
+    pub payout_mode: PayoutMode,
+    pub allowed_tokens: Option<Vec<Address>>,
+    pub remainder_policy: RemainderPolicy,
+    pub max_recipients: Option<u32>,
+    pub share_mode: ShareMode,
+    pub storage_layout: StorageLayout,
+    pub keeper_fee: KeeperFee,
+    pub min_distribution: i128,
+    pub min_ledger_gap: u32,
+}
+
 // Define the SimpleSplitter client interface
 #[contractclient(name = "SimpleSplitterClient")]
 pub trait SimpleSplitterTrait {
-    fn init(env: Env, token: Address, recipients: Vec<Address>, shares: Vec<u32>);
+    fn init_with_options(
+        env: Env,
+        token: Address,
+        recipients: Vec<Address>,
+        shares: Vec<u32>,
+        options: SplitterOptions,
+    ) -> Result<(), soroban_sdk::Error>;
 }
 
 #[contract]
@@ -21,60 +174,548 @@ pub struct SimpleSplitterFactory;
 /// Factory contract for deploying SimpleSplitter instances.
 #[contractimpl]
 impl SimpleSplitterFactory {
-    /// Initialize the factory with the SimpleSplitter WASM hash.
+    /// Initialize the factory with an admin and the SimpleSplitter WASM hash.
     /// This should be called once after deploying the factory.
-    pub fn init(env: Env, splitter_wasm_hash: BytesN<32>) {
+    /// The hash is recorded as version 1.
+    pub fn init(env: Env, admin: Address, splitter_wasm_hash: BytesN<32>) -> Result<(), Error> {
         // Prevent reinitialization
         if env.storage().instance().has(&symbol_short!("initd")) {
-            panic!("already initialized");
+            return Err(Error::AlreadyInitialized);
         }
 
         // Mark as initialized
         env.storage().instance().set(&symbol_short!("initd"), &true);
+        env.storage().instance().set(&ADMIN, &admin);
 
+        record_wasm_hash(&env, &splitter_wasm_hash);
         env.storage()
             .instance()
-            .set(&WASM_HASH, &splitter_wasm_hash);
+            .extend_ttl(ONE_YEAR_LEDGERS, ONE_YEAR_LEDGERS);
+
+        Ok(())
+    }
+
+    /// Point new splitters at a new SimpleSplitter WASM hash.
+    /// Only the admin can call this. Existing splitters are unaffected.
+    /// Returns the version number assigned to the hash.
+    pub fn set_splitter_wasm_hash(env: Env, splitter_wasm_hash: BytesN<32>) -> Result<u32, Error> {
+        let admin = Self::admin(env.clone())?;
+        admin.require_auth();
+
+        let version = record_wasm_hash(&env, &splitter_wasm_hash);
+
+        env.events()
+            .publish((symbol_short!("wasm_set"),), (version, splitter_wasm_hash));
+
         env.storage()
             .instance()
             .extend_ttl(ONE_YEAR_LEDGERS, ONE_YEAR_LEDGERS);
+
+        Ok(version)
+    }
+
+    /// The factory admin.
+    pub fn admin(env: Env) -> Result<Address, Error> {
+        env.storage()
+            .instance()
+            .get(&ADMIN)
+            .ok_or(Error::NotInitialized)
+    }
+
+    /// WASM hash used for new splitters.
+    pub fn splitter_wasm_hash(env: Env) -> Result<BytesN<32>, Error> {
+        env.storage()
+            .instance()
+            .get(&WASM_HASH)
+            .ok_or(Error::NotInitialized)
+    }
+
+    /// WASM hash registered under `version`.
+    pub fn wasm_hash_for_version(env: Env, version: u32) -> Result<BytesN<32>, Error> {
+        let entry: WasmVersion = env
+            .storage()
+            .persistent()
+            .get(&DataKey::WasmVersion(version))
+            .ok_or(Error::UnknownVersion)?;
+        Ok(entry.wasm_hash)
+    }
+
+    /// WASM version a splitter deployed by this factory was created from,
+    /// or `None` if the factory did not deploy it.
+    ///
+    /// Owners upgrade their splitters directly, so this stays the creation
+    /// version; the splitter's own `version()` reports the code it runs now.
+    pub fn splitter_version(env: Env, splitter: Address) -> Option<u32> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::SplitterVersion(splitter))
+    }
+
+    /// Version number of the current WASM hash (0 before `init`).
+    pub fn latest_version(env: Env) -> u32 {
+        env.storage().instance().get(&LATEST_VERSION).unwrap_or(0)
+    }
+
+    /// History of WASM hashes, oldest first, starting at index `start`.
+    /// Paginated like `list_splitters`.
+    pub fn wasm_versions(env: Env, start: u32, limit: u32) -> Vec<WasmVersion> {
+        let end = start
+            .saturating_add(limit.min(MAX_PAGE_SIZE))
+            .min(Self::latest_version(env.clone()));
+
+        let mut page = Vec::new(&env);
+        for index in start..end {
+            if let Some(entry) = env
+                .storage()
+                .persistent()
+                .get(&DataKey::WasmVersion(index + 1))
+            {
+                page.push_back(entry);
+            }
+        }
+        page
     }
 
     /// Create a new SimpleSplitter contract with the given parameters.
     /// Returns the address of the newly deployed contract.
     /// The salt parameter allows creating multiple splitters with the same configuration
     /// while remaining deterministic for simulation.
+    /// Each salt can only be used once.
+    /// The splitter has no owner, so its recipients and shares are immutable.
+    /// `creator` must authorize the call and is recorded in the registry.
     pub fn create(
         env: Env,
+        creator: Address,
         salt: BytesN<32>,
         token: Address,
         recipients: soroban_sdk::Vec<Address>,
         shares: soroban_sdk::Vec<u32>,
-    ) -> Address {
-        let wasm_hash: BytesN<32> = env
-            .storage()
-            .instance()
-            .get(&WASM_HASH)
-            .expect("Factory not initialized with WASM hash");
+    ) -> Result<Address, Error> {
+        Self::create_with_options(
+            env,
+            creator,
+            salt,
+            token,
+            recipients,
+            shares,
+            SplitterOptions::default(),
+        )
+    }
+
+    /// Create a new SimpleSplitter contract with optional settings such as an owner.
+    /// The factory writes one index entry per recipient, so a split created
+    /// here must stay within about `MAX_RECIPIENTS` even with
+    /// `StorageLayout::Persistent`; deploy larger splits directly.
+    pub fn create_with_options(
+        env: Env,
+        creator: Address,
+        salt: BytesN<32>,
+        token: Address,
+        recipients: soroban_sdk::Vec<Address>,
+        shares: soroban_sdk::Vec<u32>,
+        options: SplitterOptions,
+    ) -> Result<Address, Error> {
+        let version = Self::latest_version(env.clone());
+        if version == 0 {
+            return Err(Error::NotInitialized);
+        }
+
+        Self::create_with_version(
+            env, creator, version, salt, token, recipients, shares, options,
+        )
+    }
+
+    /// Create a new SimpleSplitter contract from a specific registered WASM version.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_with_version(
+        env: Env,
+        creator: Address,
+        version: u32,
+        salt: BytesN<32>,
+        token: Address,
+        recipients: soroban_sdk::Vec<Address>,
+        shares: soroban_sdk::Vec<u32>,
+        options: SplitterOptions,
+    ) -> Result<Address, Error> {
+        if Self::is_deployed(env.clone(), salt.clone()) {
+            return Err(Error::SaltAlreadyUsed);
+        }
+
+        deploy_splitter(
+            &env,
+            creator,
+            version,
+            plain_deploy_salt(&env, &salt),
+            token,
+            recipients,
+            shares,
+            options,
+        )
+    }
 
-        let contract_id = env
+    /// Create an immutable splitter whose salt is derived from its normalized
+    /// configuration, so identical splits share one contract.
+    /// Recipients are sorted, duplicates merged, zero shares dropped and shares
+    /// reduced by their greatest common divisor before hashing.
+    /// If a canonical splitter already exists for the configuration, its
+    /// address is returned and nothing is deployed.
+    pub fn create_canonical(
+        env: Env,
+        creator: Address,
+        token: Address,
+        recipients: Vec<Address>,
+        shares: Vec<u32>,
+    ) -> Result<Address, Error> {
+        let (recipients, shares) = normalize_split(&env, &recipients, &shares)?;
+        let salt = Self::canonical_salt(
+            env.clone(),
+            token.clone(),
+            recipients.clone(),
+            shares.clone(),
+        )?;
+
+        let existing = env
+            .deployer()
+            .with_current_contract(salt.clone())
+            .deployed_address();
+        if is_registered(&env, &existing) {
+            return Ok(existing);
+        }
+
+        let version = Self::latest_version(env.clone());
+        if version == 0 {
+            return Err(Error::NotInitialized);
+        }
+
+        // Deployed straight at the configuration salt. `create` hashes its
+        // salts into a separate space, so it can never take this address.
+        deploy_splitter(
+            &env,
+            creator,
+            version,
+            salt,
+            token,
+            recipients,
+            shares,
+            SplitterOptions::default(),
+        )
+    }
+
+    /// Salt `create_canonical` deploys the given configuration at. Passing it
+    /// to `create` yields a different address, since plain salts are hashed
+    /// into their own space.
+    pub fn canonical_salt(
+        env: Env,
+        token: Address,
+        recipients: Vec<Address>,
+        shares: Vec<u32>,
+    ) -> Result<BytesN<32>, Error> {
+        let (recipients, shares) = normalize_split(&env, &recipients, &shares)?;
+        let config = (symbol_short!("canonical"), token, recipients, shares).to_xdr(&env);
+        Ok(env.crypto().sha256(&config).into())
+    }
+
+    /// Address a splitter created with `salt` will be deployed to.
+    /// Payers can be given this address before the splitter exists.
+    pub fn predict_address(env: Env, salt: BytesN<32>) -> Address {
+        env.deployer()
+            .with_current_contract(plain_deploy_salt(&env, &salt))
+            .deployed_address()
+    }
+
+    /// Address of the canonical splitter for a configuration, whether or not
+    /// `create_canonical` has deployed it yet.
+    pub fn predict_canonical_address(
+        env: Env,
+        token: Address,
+        recipients: Vec<Address>,
+        shares: Vec<u32>,
+    ) -> Result<Address, Error> {
+        let salt = Self::canonical_salt(env.clone(), token, recipients, shares)?;
+        Ok(env
             .deployer()
             .with_current_contract(salt)
-            .deploy_v2(wasm_hash, Vec::<soroban_sdk::Val>::new(&env));
+            .deployed_address())
+    }
 
-        // Initialize the deployed contract using the client
-        let splitter = SimpleSplitterClient::new(&env, &contract_id);
-        splitter.init(&token, &recipients, &shares);
+    /// Whether a splitter has already been deployed with `salt`.
+    pub fn is_deployed(env: Env, salt: BytesN<32>) -> bool {
+        is_registered(&env, &Self::predict_address(env.clone(), salt))
+    }
 
-        // Emit event for better observability
-        env.events()
-            .publish((symbol_short!("created"),), &contract_id);
+    /// Number of splitters deployed by this factory.
+    pub fn splitter_count(env: Env) -> u32 {
+        env.storage().instance().get(&SPLITTER_COUNT).unwrap_or(0)
+    }
 
-        // Extend TTL to keep factory alive
-        env.storage()
-            .instance()
-            .extend_ttl(ONE_YEAR_LEDGERS, ONE_YEAR_LEDGERS);
+    /// Registry entries in creation order, starting at index `start`.
+    /// At most `limit` entries are returned, capped at `MAX_PAGE_SIZE`.
+    pub fn list_splitters(env: Env, start: u32, limit: u32) -> Vec<SplitterInfo> {
+        let end = start
+            .saturating_add(limit.min(MAX_PAGE_SIZE))
+            .min(Self::splitter_count(env.clone()));
+
+        let mut page = Vec::new(&env);
+        for index in start..end {
+            if let Some(info) = env.storage().persistent().get(&DataKey::Splitter(index)) {
+                page.push_back(info);
+            }
+        }
+        page
+    }
+
+    /// Splitters created with `recipient` among their recipients, in creation order.
+    /// Paginated like `list_splitters`.
+    pub fn splitters_for_recipient(
+        env: Env,
+        recipient: Address,
+        start: u32,
+        limit: u32,
+    ) -> Vec<Address> {
+        let (full_pages, current) = recipient_index(&env, &recipient);
+        let count = full_pages * RECIPIENT_INDEX_PAGE + current.len();
+        let end = start.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+
+        let mut page = Vec::new(&env);
+        let mut index = start;
+        while index < end {
+            let number = index / RECIPIENT_INDEX_PAGE;
+            let splitters = if number < full_pages {
+                env.storage()
+                    .persistent()
+                    .get(&DataKey::RecipientPage(recipient.clone(), number))
+                    .unwrap_or_else(|| Vec::new(&env))
+            } else {
+                current.clone()
+            };
+
+            let first = number * RECIPIENT_INDEX_PAGE;
+            let last = end.min(first + RECIPIENT_INDEX_PAGE);
+            page.append(&splitters.slice(index - first..last - first));
+            index = last;
+        }
+        page
+    }
+}
+
+/// Deployment salt for a caller-supplied `create` salt. Hashing it with a
+/// domain tag keeps plain salts apart from the salts `create_canonical`
+/// deploys at, so nobody can squat a configuration's canonical address.
+fn plain_deploy_salt(env: &Env, salt: &BytesN<32>) -> BytesN<32> {
+    let tagged = (symbol_short!("plain"), salt.clone()).to_xdr(env);
+    env.crypto().sha256(&tagged).into()
+}
+
+/// Deploy a splitter from WASM `version` at `deploy_salt`, initialize it and
+/// record it in the registry.
+#[allow(clippy::too_many_arguments)]
+fn deploy_splitter(
+    env: &Env,
+    creator: Address,
+    version: u32,
+    deploy_salt: BytesN<32>,
+    token: Address,
+    recipients: Vec<Address>,
+    shares: Vec<u32>,
+    options: SplitterOptions,
+) -> Result<Address, Error> {
+    creator.require_auth();
+
+    let wasm_hash = SimpleSplitterFactory::wasm_hash_for_version(env.clone(), version)?;
+
+    let contract_id = env
+        .deployer()
+        .with_current_contract(deploy_salt)
+        .deploy_v2(wasm_hash, Vec::<soroban_sdk::Val>::new(env));
+
+    // Initialize the deployed contract using the client. Any splitter error
+    // aborts the whole call, so the deployment is rolled back with it.
+    let splitter = SimpleSplitterClient::new(env, &contract_id);
+    match splitter.try_init_with_options(&token, &recipients, &shares, &options) {
+        Ok(Ok(())) => {}
+        Err(Ok(error)) if error.is_type(ScErrorType::Contract) => {
+            return Err(splitter_error(error.get_code()));
+        }
+        _ => return Err(Error::SplitterInitFailed),
+    }
+
+    // Record the splitter in the registry
+    let index = SimpleSplitterFactory::splitter_count(env.clone());
+    let info_key = DataKey::Splitter(index);
+    env.storage().persistent().set(
+        &info_key,
+        &SplitterInfo {
+            address: contract_id.clone(),
+            creator,
+            token,
+            ledger: env.ledger().sequence(),
+            version,
+        },
+    );
+    env.storage()
+        .persistent()
+        .extend_ttl(&info_key, ONE_YEAR_LEDGERS, ONE_YEAR_LEDGERS);
+    env.storage().instance().set(&SPLITTER_COUNT, &(index + 1));
+
+    let version_key = DataKey::SplitterVersion(contract_id.clone());
+    env.storage().persistent().set(&version_key, &version);
+    env.storage()
+        .persistent()
+        .extend_ttl(&version_key, ONE_YEAR_LEDGERS, ONE_YEAR_LEDGERS);
+    index_recipients(env, &contract_id, &recipients);
 
-        contract_id
+    // Emit event for better observability
+    env.events()
+        .publish((symbol_short!("created"),), &contract_id);
+
+    // Extend TTL to keep factory alive
+    env.storage()
+        .instance()
+        .extend_ttl(ONE_YEAR_LEDGERS, ONE_YEAR_LEDGERS);
+
+    Ok(contract_id)
+}
+
+/// Make `wasm_hash` the current splitter code under the next version number.
+fn record_wasm_hash(env: &Env, wasm_hash: &BytesN<32>) -> u32 {
+    let version = SimpleSplitterFactory::latest_version(env.clone()) + 1;
+
+    let key = DataKey::WasmVersion(version);
+    env.storage().persistent().set(
+        &key,
+        &WasmVersion {
+            version,
+            wasm_hash: wasm_hash.clone(),
+            ledger: env.ledger().sequence(),
+        },
+    );
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, ONE_YEAR_LEDGERS, ONE_YEAR_LEDGERS);
+
+    env.storage().instance().set(&WASM_HASH, wasm_hash);
+    env.storage().instance().set(&LATEST_VERSION, &version);
+
+    version
+}
+
+/// Sort recipients by address, merge duplicates, drop zero shares and divide
+/// shares by their greatest common divisor.
+fn normalize_split(
+    env: &Env,
+    recipients: &Vec<Address>,
+    shares: &Vec<u32>,
+) -> Result<(Vec<Address>, Vec<u32>), Error> {
+    if recipients.len() != shares.len() {
+        return Err(Error::LengthMismatch);
+    }
+
+    let mut sorted_recipients: Vec<Address> = Vec::new(env);
+    let mut sorted_shares: Vec<u32> = Vec::new(env);
+    for (recipient, share) in recipients.iter().zip(shares.iter()) {
+        if share == 0 {
+            continue;
+        }
+
+        // Insertion sort; recipient lists are small
+        let mut pos = sorted_recipients.len();
+        for (i, existing) in sorted_recipients.iter().enumerate() {
+            if existing >= recipient {
+                pos = i as u32;
+                break;
+            }
+        }
+
+        if sorted_recipients.get(pos) == Some(recipient.clone()) {
+            let merged = sorted_shares
+                .get_unchecked(pos)
+                .checked_add(share)
+                .ok_or(Error::InvalidSplitterConfig)?;
+            sorted_shares.set(pos, merged);
+        } else {
+            sorted_recipients.insert(pos, recipient);
+            sorted_shares.insert(pos, share);
+        }
+    }
+
+    if sorted_recipients.is_empty() {
+        return Err(Error::ZeroTotalShares);
+    }
+
+    let divisor = sorted_shares.iter().fold(0, gcd);
+    let mut reduced = Vec::new(env);
+    for share in sorted_shares.iter() {
+        reduced.push_back(share / divisor);
+    }
+
+    Ok((sorted_recipients, reduced))
+}
+
+/// Factory error for a SimpleSplitter `init` failure with contract error `code`.
+fn splitter_error(code: u32) -> Error {
+    match code {
+        3 => Error::LengthMismatch,
+        4 => Error::ZeroTotalShares,
+        11 => Error::InvalidMinDistribution,
+        15 => Error::DuplicateRecipient,
+        16 => Error::SelfRecipient,
+        17 => Error::TooManyRecipients,
+        18 => Error::InvalidBasisPoints,
+        21 => Error::InvalidKeeperFee,
+        _ => Error::InvalidSplitterConfig,
+    }
+}
+
+fn gcd(a: u32, b: u32) -> u32 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Whether this factory deployed a splitter at `address`.
+fn is_registered(env: &Env, address: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::SplitterVersion(address.clone()))
+}
+
+/// Number of full pages in `recipient`'s reverse index and the splitters of
+/// the current page.
+fn recipient_index(env: &Env, recipient: &Address) -> (u32, Vec<Address>) {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RecipientSplitters(recipient.clone()))
+        .unwrap_or_else(|| (0, Vec::new(env)))
+}
+
+/// Append `splitter` to the reverse index of each distinct recipient.
+fn index_recipients(env: &Env, splitter: &Address, recipients: &Vec<Address>) {
+    for (i, recipient) in recipients.iter().enumerate() {
+        // Splitters from older WASM versions may list a recipient twice; index it once
+        if recipients.first_index_of(&recipient) != Some(i as u32) {
+            continue;
+        }
+
+        let (mut full_pages, mut current) = recipient_index(env, &recipient);
+        current.push_back(splitter.clone());
+
+        // Move a filled page out so the current one stays small
+        if current.len() == RECIPIENT_INDEX_PAGE {
+            let page_key = DataKey::RecipientPage(recipient.clone(), full_pages);
+            env.storage().persistent().set(&page_key, &current);
+            env.storage()
+                .persistent()
+                .extend_ttl(&page_key, ONE_YEAR_LEDGERS, ONE_YEAR_LEDGERS);
+            full_pages += 1;
+            current = Vec::new(env);
+        }
+
+        let key = DataKey::RecipientSplitters(recipient);
+        env.storage().persistent().set(&key, &(full_pages, current));
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, ONE_YEAR_LEDGERS, ONE_YEAR_LEDGERS);
     }
 }
@@ -3,8 +3,8 @@
 use super::*;
 use simple_splitter::SimpleSplitterClient;
 use soroban_sdk::{
-    testutils::{Address as _, Events},
-    vec, Address, Env,
+    testutils::{Address as _, Events, Ledger},
+    token, vec, Address, Env, IntoVal, TryFromVal, Val,
 };
 
 // Import optimized SimpleSplitter WASM
@@ -14,6 +14,13 @@ mod simple_splitter_wasm {
     );
 }
 
+// SimpleSplitter with an extra `upgraded` entrypoint, to upgrade splitters to
+mod upgraded_splitter_wasm {
+    soroban_sdk::contractimport!(
+        file = "../../target/wasm32-unknown-unknown/release/simple_splitter_upgrade_test.optimized.wasm"
+    );
+}
+
 fn setup_test_env() -> Env {
     let env = Env::default();
     env.mock_all_auths();
@@ -56,14 +63,15 @@ fn test_factory_init() {
     let wasm_hash = create_dummy_wasm_hash(&env);
 
     // Initialize factory with WASM hash - should not panic
-    factory.init(&wasm_hash);
+    let admin = Address::generate(&env);
+    factory.init(&admin, &wasm_hash);
 }
 
 #[test]
-#[should_panic(expected = "Factory not initialized with WASM hash")]
 fn test_create_without_init() {
     let env = setup_test_env();
     let (_factory_id, factory) = create_factory(&env);
+    let creator = Address::generate(&env);
     let token = create_token(&env);
 
     let alice = Address::generate(&env);
@@ -71,26 +79,30 @@ fn test_create_without_init() {
 
     let salt = create_salt(&env, b"test_salt");
 
-    // This should panic because factory wasn't initialized
-    factory.create(
+    // This should fail because factory wasn't initialized
+    let result = factory.try_create(
+        &creator,
         &salt,
         &token,
         &vec![&env, alice.clone(), bob.clone()],
         &vec![&env, 1, 1],
     );
+    assert_eq!(result, Err(Ok(Error::NotInitialized)));
 }
 
 #[test]
 fn test_create_splitter_success() {
     let env = setup_test_env();
     let (_factory_id, factory) = create_factory(&env);
+    let creator = Address::generate(&env);
     let token = create_token(&env);
 
     // Get SimpleSplitter WASM hash
     let splitter_wasm_hash = get_splitter_wasm_hash(&env);
 
     // Initialize factory with the SimpleSplitter WASM hash
-    factory.init(&splitter_wasm_hash);
+    let admin = Address::generate(&env);
+    factory.init(&admin, &splitter_wasm_hash);
 
     let alice = Address::generate(&env);
     let bob = Address::generate(&env);
@@ -98,6 +110,7 @@ fn test_create_splitter_success() {
 
     // Create a new splitter
     let splitter_address = factory.create(
+        &creator,
         &salt,
         &token,
         &vec![&env, alice.clone(), bob.clone()],
@@ -106,28 +119,30 @@ fn test_create_splitter_success() {
 
     // Verify the splitter contract was deployed and initialized correctly
     let splitter_client = SimpleSplitterClient::new(&env, &splitter_address);
-    let (config_token, config_recipients, config_shares) = splitter_client.get_config();
+    let config = splitter_client.get_config();
 
-    assert_eq!(config_token, token);
-    assert_eq!(config_recipients.len(), 2);
-    assert_eq!(config_recipients.get(0).unwrap(), alice);
-    assert_eq!(config_recipients.get(1).unwrap(), bob);
-    assert_eq!(config_shares.len(), 2);
-    assert_eq!(config_shares.get(0).unwrap(), 1);
-    assert_eq!(config_shares.get(1).unwrap(), 1);
+    assert_eq!(config.token, token);
+    assert_eq!(config.recipients.len(), 2);
+    assert_eq!(config.recipients.get(0).unwrap(), alice);
+    assert_eq!(config.recipients.get(1).unwrap(), bob);
+    assert_eq!(config.shares.len(), 2);
+    assert_eq!(config.shares.get(0).unwrap(), 1);
+    assert_eq!(config.shares.get(1).unwrap(), 1);
 }
 
 #[test]
 fn test_create_multiple_splitters() {
     let env = setup_test_env();
     let (_factory_id, factory) = create_factory(&env);
+    let creator = Address::generate(&env);
     let token = create_token(&env);
 
     // Get SimpleSplitter WASM hash
     let splitter_wasm_hash = get_splitter_wasm_hash(&env);
 
     // Initialize factory
-    factory.init(&splitter_wasm_hash);
+    let admin = Address::generate(&env);
+    factory.init(&admin, &splitter_wasm_hash);
 
     let alice = Address::generate(&env);
     let bob = Address::generate(&env);
@@ -136,6 +151,7 @@ fn test_create_multiple_splitters() {
     // Create first splitter
     let salt1 = create_salt(&env, b"splitter_1");
     let splitter1 = factory.create(
+        &creator,
         &salt1,
         &token,
         &vec![&env, alice.clone(), bob.clone()],
@@ -145,6 +161,7 @@ fn test_create_multiple_splitters() {
     // Create second splitter with same parameters but different salt - should get different address
     let salt2 = create_salt(&env, b"splitter_2");
     let splitter2 = factory.create(
+        &creator,
         &salt2,
         &token,
         &vec![&env, alice.clone(), bob.clone()],
@@ -154,6 +171,7 @@ fn test_create_multiple_splitters() {
     // Create third splitter with different parameters
     let salt3 = create_salt(&env, b"splitter_3");
     let splitter3 = factory.create(
+        &creator,
         &salt3,
         &token,
         &vec![&env, alice.clone(), bob.clone(), charlie.clone()],
@@ -167,24 +185,26 @@ fn test_create_multiple_splitters() {
 
     // Verify third splitter has correct config
     let splitter3_client = SimpleSplitterClient::new(&env, &splitter3);
-    let (_, config_recipients, config_shares) = splitter3_client.get_config();
-    assert_eq!(config_recipients.len(), 3);
-    assert_eq!(config_shares.get(0).unwrap(), 1);
-    assert_eq!(config_shares.get(1).unwrap(), 2);
-    assert_eq!(config_shares.get(2).unwrap(), 3);
+    let config = splitter3_client.get_config();
+    assert_eq!(config.recipients.len(), 3);
+    assert_eq!(config.shares.get(0).unwrap(), 1);
+    assert_eq!(config.shares.get(1).unwrap(), 2);
+    assert_eq!(config.shares.get(2).unwrap(), 3);
 }
 
 #[test]
 fn test_create_emits_event() {
     let env = setup_test_env();
     let (_factory_id, factory) = create_factory(&env);
+    let creator = Address::generate(&env);
     let token = create_token(&env);
 
     // Get SimpleSplitter WASM hash
     let splitter_wasm_hash = get_splitter_wasm_hash(&env);
 
     // Initialize factory
-    factory.init(&splitter_wasm_hash);
+    let admin = Address::generate(&env);
+    factory.init(&admin, &splitter_wasm_hash);
 
     let alice = Address::generate(&env);
     let bob = Address::generate(&env);
@@ -192,6 +212,7 @@ fn test_create_emits_event() {
 
     // Create a new splitter
     let _splitter_address = factory.create(
+        &creator,
         &salt,
         &token,
         &vec![&env, alice.clone(), bob.clone()],
@@ -207,40 +228,1251 @@ fn test_create_emits_event() {
 fn test_create_single_recipient() {
     let env = setup_test_env();
     let (_factory_id, factory) = create_factory(&env);
+    let creator = Address::generate(&env);
     let token = create_token(&env);
 
     // Get SimpleSplitter WASM hash
     let splitter_wasm_hash = get_splitter_wasm_hash(&env);
 
     // Initialize factory
-    factory.init(&splitter_wasm_hash);
+    let admin = Address::generate(&env);
+    factory.init(&admin, &splitter_wasm_hash);
 
     let alice = Address::generate(&env);
     let salt = create_salt(&env, b"single_recipient");
 
     // Create a splitter with single recipient
-    let splitter_address =
-        factory.create(&salt, &token, &vec![&env, alice.clone()], &vec![&env, 100]);
+    let splitter_address = factory.create(
+        &creator,
+        &salt,
+        &token,
+        &vec![&env, alice.clone()],
+        &vec![&env, 100],
+    );
 
     // Verify configuration
     let splitter_client = SimpleSplitterClient::new(&env, &splitter_address);
-    let (_, config_recipients, config_shares) = splitter_client.get_config();
-    assert_eq!(config_recipients.len(), 1);
-    assert_eq!(config_recipients.get(0).unwrap(), alice);
-    assert_eq!(config_shares.get(0).unwrap(), 100);
+    let config = splitter_client.get_config();
+    assert_eq!(config.recipients.len(), 1);
+    assert_eq!(config.recipients.get(0).unwrap(), alice);
+    assert_eq!(config.shares.get(0).unwrap(), 100);
 }
 
 #[test]
-#[should_panic(expected = "already initialized")]
 fn test_cannot_reinitialize_factory() {
     let env = setup_test_env();
     let (_factory_id, factory) = create_factory(&env);
     let wasm_hash = create_dummy_wasm_hash(&env);
 
     // First initialization should succeed
-    factory.init(&wasm_hash);
+    let admin = Address::generate(&env);
+    factory.init(&admin, &wasm_hash);
 
-    // Second initialization should panic
+    // Second initialization should fail
     let another_hash = create_dummy_wasm_hash(&env);
-    factory.init(&another_hash);
+    assert_eq!(
+        factory.try_init(&admin, &another_hash),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+}
+
+#[test]
+fn test_create_with_invalid_config() {
+    let env = setup_test_env();
+    let (_factory_id, factory) = create_factory(&env);
+    let creator = Address::generate(&env);
+    let token = create_token(&env);
+
+    let admin = Address::generate(&env);
+    factory.init(&admin, &get_splitter_wasm_hash(&env));
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    // Length mismatch is rejected by the splitter's init
+    let result = factory.try_create(
+        &creator,
+        &create_salt(&env, b"mismatch"),
+        &token,
+        &vec![&env, alice.clone(), bob.clone()],
+        &vec![&env, 1],
+    );
+    assert_eq!(result, Err(Ok(Error::LengthMismatch)));
+
+    // Zero total shares is rejected by the splitter's init
+    let result = factory.try_create(
+        &creator,
+        &create_salt(&env, b"zero_shares"),
+        &token,
+        &vec![&env, alice.clone(), bob.clone()],
+        &vec![&env, 0, 0],
+    );
+    assert_eq!(result, Err(Ok(Error::ZeroTotalShares)));
+
+    // Option checks keep the splitter's reason too
+    let cases = [
+        (
+            SplitterOptions {
+                max_recipients: Some(1),
+                ..Default::default()
+            },
+            Error::TooManyRecipients,
+        ),
+        (
+            SplitterOptions {
+                keeper_fee: KeeperFee::Fixed(-1),
+                ..Default::default()
+            },
+            Error::InvalidKeeperFee,
+        ),
+        (
+            SplitterOptions {
+                min_distribution: -1,
+                ..Default::default()
+            },
+            Error::InvalidMinDistribution,
+        ),
+    ];
+    for (options, error) in cases {
+        let result = factory.try_create_with_options(
+            &creator,
+            &create_salt(&env, b"options"),
+            &token,
+            &vec![&env, alice.clone(), bob.clone()],
+            &vec![&env, 1, 1],
+            &options,
+        );
+        assert_eq!(result, Err(Ok(error)));
+    }
+}
+
+#[test]
+fn test_create_with_used_salt() {
+    let env = setup_test_env();
+    let (_factory_id, factory) = create_factory(&env);
+    let creator = Address::generate(&env);
+    let token = create_token(&env);
+
+    let admin = Address::generate(&env);
+    factory.init(&admin, &get_splitter_wasm_hash(&env));
+
+    let alice = Address::generate(&env);
+    let salt = create_salt(&env, b"reused");
+
+    factory.create(
+        &creator,
+        &salt,
+        &token,
+        &vec![&env, alice.clone()],
+        &vec![&env, 1],
+    );
+
+    let result = factory.try_create(
+        &creator,
+        &salt,
+        &token,
+        &vec![&env, alice.clone()],
+        &vec![&env, 1],
+    );
+    assert_eq!(result, Err(Ok(Error::SaltAlreadyUsed)));
+}
+
+#[test]
+fn test_salt_reusable_after_failed_create() {
+    let env = setup_test_env();
+    let (_factory_id, factory) = create_factory(&env);
+    let creator = Address::generate(&env);
+    let token = create_token(&env);
+
+    let admin = Address::generate(&env);
+    factory.init(&admin, &get_splitter_wasm_hash(&env));
+
+    let alice = Address::generate(&env);
+    let salt = create_salt(&env, b"retry");
+
+    // A failed create must not consume the salt
+    let result = factory.try_create(
+        &creator,
+        &salt,
+        &token,
+        &vec![&env, alice.clone()],
+        &vec![&env, 0],
+    );
+    assert_eq!(result, Err(Ok(Error::ZeroTotalShares)));
+
+    factory.create(
+        &creator,
+        &salt,
+        &token,
+        &vec![&env, alice.clone()],
+        &vec![&env, 1],
+    );
+}
+
+#[test]
+fn test_create_with_owner() {
+    let env = setup_test_env();
+    let (_factory_id, factory) = create_factory(&env);
+    let creator = Address::generate(&env);
+    let token = create_token(&env);
+
+    let admin = Address::generate(&env);
+    factory.init(&admin, &get_splitter_wasm_hash(&env));
+
+    let owner = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    let splitter_address = factory.create_with_options(
+        &creator,
+        &create_salt(&env, b"owned"),
+        &token,
+        &vec![&env, alice.clone()],
+        &vec![&env, 1],
+        &SplitterOptions {
+            owner: Some(owner.clone()),
+            ..Default::default()
+        },
+    );
+
+    let splitter_client = SimpleSplitterClient::new(&env, &splitter_address);
+    assert_eq!(splitter_client.owner(), Some(owner));
+
+    // The owner can change the split after deployment
+    splitter_client.update_recipients(&vec![&env, alice.clone(), bob.clone()], &vec![&env, 1, 1]);
+    let config = splitter_client.get_config();
+    assert_eq!(config.recipients, vec![&env, alice.clone(), bob.clone()]);
+}
+
+#[test]
+fn test_create_without_owner_is_immutable() {
+    let env = setup_test_env();
+    let (_factory_id, factory) = create_factory(&env);
+    let creator = Address::generate(&env);
+    let token = create_token(&env);
+
+    let admin = Address::generate(&env);
+    factory.init(&admin, &get_splitter_wasm_hash(&env));
+
+    let alice = Address::generate(&env);
+
+    let splitter_address = factory.create(
+        &creator,
+        &create_salt(&env, b"unowned"),
+        &token,
+        &vec![&env, alice.clone()],
+        &vec![&env, 1],
+    );
+
+    let splitter_client = SimpleSplitterClient::new(&env, &splitter_address);
+    assert_eq!(splitter_client.owner(), None);
+    assert_eq!(
+        splitter_client.try_update_recipients(&vec![&env, alice.clone()], &vec![&env, 2]),
+        Err(Ok(simple_splitter::Error::Immutable))
+    );
+}
+
+#[test]
+fn test_create_with_options_round_trips() {
+    let env = setup_test_env();
+    let (_factory_id, factory) = create_factory(&env);
+    let creator = Address::generate(&env);
+    let token = create_token(&env);
+
+    let admin = Address::generate(&env);
+    factory.init(&admin, &get_splitter_wasm_hash(&env));
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let cases = [
+        SplitterOptions::default(),
+        SplitterOptions {
+            owner: Some(Address::generate(&env)),
+            guardian: Some(Address::generate(&env)),
+            ..Default::default()
+        },
+        SplitterOptions {
+            payout_mode: PayoutMode::Claim,
+            allowed_tokens: Some(vec![&env, create_token(&env)]),
+            ..Default::default()
+        },
+        SplitterOptions {
+            remainder_policy: RemainderPolicy::FirstRecipient,
+            max_recipients: Some(2),
+            ..Default::default()
+        },
+        SplitterOptions {
+            share_mode: ShareMode::BasisPoints,
+            storage_layout: StorageLayout::Persistent,
+            ..Default::default()
+        },
+        SplitterOptions {
+            keeper_fee: KeeperFee::BasisPoints(50),
+            min_distribution: 100,
+            min_ledger_gap: 12,
+            ..Default::default()
+        },
+    ];
+
+    // Every option reaches the splitter unchanged; the splitter crate tests
+    // what each of them does
+    for (i, options) in cases.iter().enumerate() {
+        let splitter_address = factory.create_with_options(
+            &creator,
+            &create_salt(&env, &[i as u8]),
+            &token,
+            &vec![&env, alice.clone(), bob.clone()],
+            &vec![&env, 5_000, 5_000],
+            options,
+        );
+        let config = SimpleSplitterClient::new(&env, &splitter_address).get_config();
+
+        let val: Val = options.into_val(&env);
+        let expected = simple_splitter::SplitterOptions::try_from_val(&env, &val).unwrap();
+        assert_eq!(config.owner, expected.owner);
+        assert_eq!(config.guardian, expected.guardian);
+        assert_eq!(config.payout_mode, expected.payout_mode);
+        assert_eq!(config.allowed_tokens, expected.allowed_tokens);
+        assert_eq!(config.remainder_policy, expected.remainder_policy);
+        let layout_cap = match expected.storage_layout {
+            simple_splitter::StorageLayout::Instance => simple_splitter::MAX_RECIPIENTS,
+            simple_splitter::StorageLayout::Persistent => {
+                simple_splitter::MAX_PERSISTENT_RECIPIENTS
+            }
+        };
+        assert_eq!(
+            config.max_recipients,
+            expected.max_recipients.unwrap_or(layout_cap)
+        );
+        assert_eq!(config.share_mode, expected.share_mode);
+        assert_eq!(config.storage_layout, expected.storage_layout);
+        assert_eq!(config.keeper_fee, expected.keeper_fee);
+        assert_eq!(config.min_distribution, expected.min_distribution);
+        assert_eq!(config.min_ledger_gap, expected.min_ledger_gap);
+    }
+}
+
+#[test]
+fn test_registry_records_created_splitters() {
+    let env = setup_test_env();
+    let (_factory_id, factory) = create_factory(&env);
+    let pyusd = create_token(&env);
+    let usdc = create_token(&env);
+
+    let admin = Address::generate(&env);
+    factory.init(&admin, &get_splitter_wasm_hash(&env));
+    assert_eq!(factory.splitter_count(), 0);
+    assert_eq!(factory.list_splitters(&0, &10), vec![&env]);
+
+    let creator1 = Address::generate(&env);
+    let creator2 = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    let splitter1 = factory.create(
+        &creator1,
+        &create_salt(&env, b"registry_1"),
+        &pyusd,
+        &vec![&env, alice.clone()],
+        &vec![&env, 1],
+    );
+    let ledger1 = env.ledger().sequence();
+
+    env.ledger().with_mut(|li| li.sequence_number += 10);
+
+    let splitter2 = factory.create(
+        &creator2,
+        &create_salt(&env, b"registry_2"),
+        &usdc,
+        &vec![&env, alice.clone()],
+        &vec![&env, 1],
+    );
+    let ledger2 = env.ledger().sequence();
+
+    assert_eq!(factory.splitter_count(), 2);
+    assert_eq!(
+        factory.list_splitters(&0, &10),
+        vec![
+            &env,
+            SplitterInfo {
+                address: splitter1.clone(),
+                creator: creator1.clone(),
+                token: pyusd.clone(),
+                ledger: ledger1,
+                version: 1,
+            },
+            SplitterInfo {
+                address: splitter2.clone(),
+                creator: creator2.clone(),
+                token: usdc.clone(),
+                ledger: ledger2,
+                version: 1,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_list_splitters_pagination() {
+    let env = setup_test_env();
+    let (_factory_id, factory) = create_factory(&env);
+    let creator = Address::generate(&env);
+    let token = create_token(&env);
+
+    let admin = Address::generate(&env);
+    factory.init(&admin, &get_splitter_wasm_hash(&env));
+
+    let alice = Address::generate(&env);
+    let mut created = vec![&env];
+    for seed in [b"page_0", b"page_1", b"page_2", b"page_3", b"page_4"] {
+        created.push_back(factory.create(
+            &creator,
+            &create_salt(&env, seed),
+            &token,
+            &vec![&env, alice.clone()],
+            &vec![&env, 1],
+        ));
+    }
+
+    let addresses = |page: soroban_sdk::Vec<SplitterInfo>| {
+        let mut out = vec![&env];
+        for info in page.iter() {
+            out.push_back(info.address);
+        }
+        out
+    };
+
+    assert_eq!(factory.splitter_count(), 5);
+    assert_eq!(
+        addresses(factory.list_splitters(&0, &2)),
+        created.slice(0..2)
+    );
+    assert_eq!(
+        addresses(factory.list_splitters(&2, &2)),
+        created.slice(2..4)
+    );
+    assert_eq!(
+        addresses(factory.list_splitters(&4, &2)),
+        created.slice(4..5)
+    );
+    assert_eq!(factory.list_splitters(&5, &2).len(), 0);
+    assert_eq!(factory.list_splitters(&u32::MAX, &u32::MAX).len(), 0);
+    assert_eq!(factory.list_splitters(&0, &0).len(), 0);
+}
+
+#[test]
+fn test_create_requires_creator_auth() {
+    let env = setup_test_env();
+    let (_factory_id, factory) = create_factory(&env);
+    let creator = Address::generate(&env);
+    let token = create_token(&env);
+
+    let admin = Address::generate(&env);
+    factory.init(&admin, &get_splitter_wasm_hash(&env));
+
+    let alice = Address::generate(&env);
+    factory.create(
+        &creator,
+        &create_salt(&env, b"auth"),
+        &token,
+        &vec![&env, alice.clone()],
+        &vec![&env, 1],
+    );
+
+    assert_eq!(env.auths().len(), 1);
+    assert_eq!(env.auths()[0].0, creator);
+}
+
+#[test]
+fn test_failed_create_is_not_registered() {
+    let env = setup_test_env();
+    let (_factory_id, factory) = create_factory(&env);
+    let creator = Address::generate(&env);
+    let token = create_token(&env);
+
+    let admin = Address::generate(&env);
+    factory.init(&admin, &get_splitter_wasm_hash(&env));
+
+    let alice = Address::generate(&env);
+    let result = factory.try_create(
+        &creator,
+        &create_salt(&env, b"invalid"),
+        &token,
+        &vec![&env, alice.clone()],
+        &vec![&env, 0],
+    );
+    assert_eq!(result, Err(Ok(Error::ZeroTotalShares)));
+    assert_eq!(factory.splitter_count(), 0);
+}
+
+#[test]
+fn test_splitters_for_recipient() {
+    let env = setup_test_env();
+    let (_factory_id, factory) = create_factory(&env);
+    let creator = Address::generate(&env);
+    let token = create_token(&env);
+
+    let admin = Address::generate(&env);
+    factory.init(&admin, &get_splitter_wasm_hash(&env));
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+
+    let splitter1 = factory.create(
+        &creator,
+        &create_salt(&env, b"recipient_1"),
+        &token,
+        &vec![&env, alice.clone(), bob.clone()],
+        &vec![&env, 1, 1],
+    );
+    let splitter2 = factory.create(
+        &creator,
+        &create_salt(&env, b"recipient_2"),
+        &token,
+        &vec![&env, bob.clone(), carol.clone()],
+        &vec![&env, 1, 1],
+    );
+    let splitter3 = factory.create(
+        &creator,
+        &create_salt(&env, b"recipient_3"),
+        &token,
+        &vec![&env, bob.clone(), alice.clone()],
+        &vec![&env, 1, 1],
+    );
+
+    assert_eq!(
+        factory.splitters_for_recipient(&alice, &0, &10),
+        vec![&env, splitter1.clone(), splitter3.clone()]
+    );
+    assert_eq!(
+        factory.splitters_for_recipient(&bob, &0, &10),
+        vec![
+            &env,
+            splitter1.clone(),
+            splitter2.clone(),
+            splitter3.clone()
+        ]
+    );
+    assert_eq!(
+        factory.splitters_for_recipient(&carol, &0, &10),
+        vec![&env, splitter2.clone()]
+    );
+
+    // Pagination
+    assert_eq!(
+        factory.splitters_for_recipient(&bob, &1, &1),
+        vec![&env, splitter2.clone()]
+    );
+    assert_eq!(
+        factory.splitters_for_recipient(&bob, &2, &10),
+        vec![&env, splitter3.clone()]
+    );
+    assert_eq!(factory.splitters_for_recipient(&bob, &3, &10).len(), 0);
+
+    // Unknown recipient
+    let dave = Address::generate(&env);
+    assert_eq!(factory.splitters_for_recipient(&dave, &0, &10).len(), 0);
+}
+
+#[test]
+fn test_failed_create_is_not_indexed_for_recipient() {
+    let env = setup_test_env();
+    let (_factory_id, factory) = create_factory(&env);
+    let creator = Address::generate(&env);
+    let token = create_token(&env);
+
+    let admin = Address::generate(&env);
+    factory.init(&admin, &get_splitter_wasm_hash(&env));
+
+    let alice = Address::generate(&env);
+    let result = factory.try_create(
+        &creator,
+        &create_salt(&env, b"invalid"),
+        &token,
+        &vec![&env, alice.clone()],
+        &vec![&env, 0],
+    );
+    assert_eq!(result, Err(Ok(Error::ZeroTotalShares)));
+    assert_eq!(factory.splitters_for_recipient(&alice, &0, &10).len(), 0);
+}
+
+#[test]
+fn test_splitters_for_recipient_across_index_pages() {
+    let env = setup_test_env();
+    let (_factory_id, factory) = create_factory(&env);
+    let creator = Address::generate(&env);
+    let token = create_token(&env);
+
+    let admin = Address::generate(&env);
+    factory.init(&admin, &get_splitter_wasm_hash(&env));
+
+    let alice = Address::generate(&env);
+    let mut splitters = vec![&env];
+    for i in 0..RECIPIENT_INDEX_PAGE + 5 {
+        splitters.push_back(factory.create(
+            &creator,
+            &create_salt(&env, &i.to_be_bytes()),
+            &token,
+            &vec![&env, alice.clone(), Address::generate(&env)],
+            &vec![&env, 1, 1],
+        ));
+    }
+
+    // Pages spanning the full page and the current one
+    assert_eq!(
+        factory.splitters_for_recipient(&alice, &0, &MAX_PAGE_SIZE),
+        splitters.slice(0..MAX_PAGE_SIZE)
+    );
+    assert_eq!(
+        factory.splitters_for_recipient(&alice, &95, &MAX_PAGE_SIZE),
+        splitters.slice(95..RECIPIENT_INDEX_PAGE + 5)
+    );
+    assert_eq!(
+        factory.splitters_for_recipient(&alice, &RECIPIENT_INDEX_PAGE, &1),
+        splitters.slice(RECIPIENT_INDEX_PAGE..RECIPIENT_INDEX_PAGE + 1)
+    );
+}
+
+#[test]
+fn test_create_with_max_recipients_fits_budget() {
+    let env = setup_test_env();
+    let (_factory_id, factory) = create_factory(&env);
+    let creator = Address::generate(&env);
+    let token = create_token(&env);
+
+    let admin = Address::generate(&env);
+    factory.init(&admin, &get_splitter_wasm_hash(&env));
+
+    let mut recipients = vec![&env];
+    let mut shares = vec![&env];
+    for _ in 0..simple_splitter::MAX_RECIPIENTS {
+        recipients.push_back(Address::generate(&env));
+        shares.push_back(1);
+    }
+
+    // Deploying, the registry and one index entry per recipient must fit
+    // Soroban's per-transaction limits
+    let splitter_address = factory.create(
+        &creator,
+        &create_salt(&env, b"max_recipients"),
+        &token,
+        &recipients,
+        &shares,
+    );
+    let resources = env.cost_estimate().resources();
+    assert!(resources.write_entries <= 25, "{resources:?}");
+    assert!(resources.read_entries <= 40, "{resources:?}");
+    assert!(resources.instructions <= 100_000_000, "{resources:?}");
+
+    for recipient in recipients.iter() {
+        assert_eq!(
+            factory.splitters_for_recipient(&recipient, &0, &10),
+            vec![&env, splitter_address.clone()]
+        );
+    }
+}
+
+#[test]
+fn test_predict_address_matches_deployment() {
+    let env = setup_test_env();
+    let keeper = Address::generate(&env);
+    let (_factory_id, factory) = create_factory(&env);
+    let creator = Address::generate(&env);
+    let token = create_token(&env);
+
+    let admin = Address::generate(&env);
+    factory.init(&admin, &get_splitter_wasm_hash(&env));
+
+    let salt = create_salt(&env, b"predict");
+    let predicted = factory.predict_address(&salt);
+    assert!(!factory.is_deployed(&salt));
+
+    // Funds sent before deployment are distributable once the splitter exists
+    let token_admin = token::StellarAssetClient::new(&env, &token);
+    token_admin.mint(&predicted, &1000);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let splitter_address = factory.create(
+        &creator,
+        &salt,
+        &token,
+        &vec![&env, alice.clone(), bob.clone()],
+        &vec![&env, 1, 1],
+    );
+    assert_eq!(splitter_address, predicted);
+    assert!(factory.is_deployed(&salt));
+
+    SimpleSplitterClient::new(&env, &splitter_address).distribute(&keeper);
+    let token_client = token::Client::new(&env, &token);
+    assert_eq!(token_client.balance(&alice), 500);
+    assert_eq!(token_client.balance(&bob), 500);
+
+    // Other salts predict different addresses
+    assert_ne!(
+        factory.predict_address(&create_salt(&env, b"other")),
+        predicted
+    );
+}
+
+#[test]
+fn test_is_deployed_false_after_failed_create() {
+    let env = setup_test_env();
+    let (_factory_id, factory) = create_factory(&env);
+    let creator = Address::generate(&env);
+    let token = create_token(&env);
+
+    let admin = Address::generate(&env);
+    factory.init(&admin, &get_splitter_wasm_hash(&env));
+
+    let salt = create_salt(&env, b"invalid");
+    let alice = Address::generate(&env);
+    let result = factory.try_create(
+        &creator,
+        &salt,
+        &token,
+        &vec![&env, alice.clone()],
+        &vec![&env, 0],
+    );
+    assert_eq!(result, Err(Ok(Error::ZeroTotalShares)));
+    assert!(!factory.is_deployed(&salt));
+}
+
+#[test]
+fn test_create_canonical_deduplicates_identical_splits() {
+    let env = setup_test_env();
+    let (_factory_id, factory) = create_factory(&env);
+    let creator = Address::generate(&env);
+    let token = create_token(&env);
+
+    let admin = Address::generate(&env);
+    factory.init(&admin, &get_splitter_wasm_hash(&env));
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+
+    let splitter = factory.create_canonical(
+        &creator,
+        &token,
+        &vec![&env, alice.clone(), bob.clone()],
+        &vec![&env, 1, 1],
+    );
+    assert_eq!(factory.splitter_count(), 1);
+
+    // Reordered, scaled, duplicated and zero-share variants all normalize to 1:1
+    let variants = [
+        (vec![&env, bob.clone(), alice.clone()], vec![&env, 1, 1]),
+        (vec![&env, alice.clone(), bob.clone()], vec![&env, 50, 50]),
+        (
+            vec![&env, alice.clone(), bob.clone(), alice.clone()],
+            vec![&env, 1, 2, 1],
+        ),
+        (
+            vec![&env, alice.clone(), carol.clone(), bob.clone()],
+            vec![&env, 3, 0, 3],
+        ),
+    ];
+    for (recipients, shares) in variants {
+        let other_creator = Address::generate(&env);
+        assert_eq!(
+            factory.create_canonical(&other_creator, &token, &recipients, &shares),
+            splitter
+        );
+    }
+    assert_eq!(factory.splitter_count(), 1);
+
+    // The deployed splitter holds the normalized configuration
+    let config = SimpleSplitterClient::new(&env, &splitter).get_config();
+    let mut sorted = vec![&env, alice.clone(), bob.clone()];
+    if bob < alice {
+        sorted = vec![&env, bob.clone(), alice.clone()];
+    }
+    assert_eq!(config.recipients, sorted);
+    assert_eq!(config.shares, vec![&env, 1, 1]);
+    assert_eq!(config.owner, None);
+
+    // A different ratio or token gets its own splitter
+    let uneven = factory.create_canonical(
+        &creator,
+        &token,
+        &vec![&env, alice.clone(), bob.clone()],
+        &vec![&env, 2, 1],
+    );
+    assert_ne!(uneven, splitter);
+
+    let other_token = create_token(&env);
+    let other = factory.create_canonical(
+        &creator,
+        &other_token,
+        &vec![&env, alice.clone(), bob.clone()],
+        &vec![&env, 1, 1],
+    );
+    assert_ne!(other, splitter);
+    assert_eq!(factory.splitter_count(), 3);
+}
+
+#[test]
+fn test_create_canonical_invalid_config() {
+    let env = setup_test_env();
+    let (_factory_id, factory) = create_factory(&env);
+    let creator = Address::generate(&env);
+    let token = create_token(&env);
+
+    let admin = Address::generate(&env);
+    factory.init(&admin, &get_splitter_wasm_hash(&env));
+
+    let alice = Address::generate(&env);
+    assert_eq!(
+        factory.try_create_canonical(&creator, &token, &vec![&env, alice.clone()], &vec![&env, 0]),
+        Err(Ok(Error::ZeroTotalShares))
+    );
+    assert_eq!(
+        factory.try_create_canonical(&creator, &token, &vec![&env, alice.clone()], &vec![&env]),
+        Err(Ok(Error::LengthMismatch))
+    );
+    assert_eq!(
+        factory.try_create_canonical(
+            &creator,
+            &token,
+            &vec![&env, alice.clone(), alice.clone()],
+            &vec![&env, u32::MAX, 1]
+        ),
+        Err(Ok(Error::InvalidSplitterConfig))
+    );
+    assert_eq!(factory.splitter_count(), 0);
+}
+
+#[test]
+fn test_plain_create_cannot_squat_canonical_address() {
+    let env = setup_test_env();
+    let (_factory_id, factory) = create_factory(&env);
+    let creator = Address::generate(&env);
+    let token = create_token(&env);
+
+    let admin = Address::generate(&env);
+    factory.init(&admin, &get_splitter_wasm_hash(&env));
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let recipients = vec![&env, alice.clone(), bob.clone()];
+    let shares = vec![&env, 1, 1];
+    let canonical = factory.predict_canonical_address(&token, &recipients, &shares);
+
+    // Someone tries to squat the canonical salt with their own configuration
+    let salt = factory.canonical_salt(&token, &recipients, &shares);
+    let squatter = factory.create_with_options(
+        &creator,
+        &salt,
+        &token,
+        &vec![&env, creator.clone()],
+        &vec![&env, 1],
+        &SplitterOptions {
+            owner: Some(creator.clone()),
+            ..Default::default()
+        },
+    );
+    assert_ne!(squatter, canonical);
+
+    // The configuration still gets its own canonical splitter
+    let splitter = factory.create_canonical(&creator, &token, &recipients, &shares);
+    assert_eq!(splitter, canonical);
+    let config = SimpleSplitterClient::new(&env, &splitter).get_config();
+    assert!(config.recipients.contains(&alice) && config.recipients.contains(&bob));
+    assert_eq!(config.owner, None);
+}
+
+#[test]
+fn test_init_records_admin_and_first_version() {
+    let env = setup_test_env();
+    let (_factory_id, factory) = create_factory(&env);
+    let admin = Address::generate(&env);
+    let wasm_hash = get_splitter_wasm_hash(&env);
+
+    assert_eq!(factory.try_admin(), Err(Ok(Error::NotInitialized)));
+    assert_eq!(factory.latest_version(), 0);
+
+    factory.init(&admin, &wasm_hash);
+
+    assert_eq!(factory.admin(), admin);
+    assert_eq!(factory.splitter_wasm_hash(), wasm_hash);
+    assert_eq!(factory.latest_version(), 1);
+    assert_eq!(
+        factory.wasm_versions(&0, &10),
+        vec![
+            &env,
+            WasmVersion {
+                version: 1,
+                wasm_hash: wasm_hash.clone(),
+                ledger: env.ledger().sequence(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_set_splitter_wasm_hash() {
+    let env = setup_test_env();
+    let (factory_id, factory) = create_factory(&env);
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let token = create_token(&env);
+    let original_hash = get_splitter_wasm_hash(&env);
+    let new_hash = create_dummy_wasm_hash(&env);
+
+    factory.init(&admin, &original_hash);
+
+    env.ledger().with_mut(|li| li.sequence_number += 5);
+    assert_eq!(factory.set_splitter_wasm_hash(&new_hash), 2);
+
+    // Check the event before any further invocation
+    let events = env.events().all();
+    assert_eq!(
+        events,
+        vec![
+            &env,
+            (
+                factory_id.clone(),
+                (symbol_short!("wasm_set"),).into_val(&env),
+                (2u32, new_hash.clone()).into_val(&env),
+            ),
+        ]
+    );
+
+    assert_eq!(env.auths()[0].0, admin);
+    assert_eq!(factory.splitter_wasm_hash(), new_hash);
+    assert_eq!(factory.latest_version(), 2);
+
+    // Switching back registers another version; history keeps every hash
+    assert_eq!(factory.set_splitter_wasm_hash(&original_hash), 3);
+    let history = factory.wasm_versions(&0, &10);
+    assert_eq!(history.len(), 3);
+    assert_eq!(history.get_unchecked(0).wasm_hash, original_hash);
+    assert_eq!(history.get_unchecked(1).wasm_hash, new_hash);
+    assert_eq!(history.get_unchecked(1).ledger, env.ledger().sequence());
+    assert_eq!(history.get_unchecked(2).wasm_hash, original_hash);
+    assert_eq!(factory.wasm_versions(&1, &1), history.slice(1..2));
+
+    // New splitters use the current hash and the factory address is unchanged
+    let alice = Address::generate(&env);
+    let splitter_address = factory.create(
+        &creator,
+        &create_salt(&env, b"after_update"),
+        &token,
+        &vec![&env, alice.clone()],
+        &vec![&env, 1],
+    );
+    assert_eq!(
+        SimpleSplitterClient::new(&env, &splitter_address)
+            .get_config()
+            .recipients,
+        vec![&env, alice]
+    );
+}
+
+#[test]
+fn test_set_splitter_wasm_hash_requires_admin() {
+    let env = setup_test_env();
+    let (_factory_id, factory) = create_factory(&env);
+    let admin = Address::generate(&env);
+    let wasm_hash = get_splitter_wasm_hash(&env);
+
+    assert_eq!(
+        factory.try_set_splitter_wasm_hash(&wasm_hash),
+        Err(Ok(Error::NotInitialized))
+    );
+
+    factory.init(&admin, &wasm_hash);
+
+    // Without the admin's signature the update is rejected
+    env.set_auths(&[]);
+    assert!(factory
+        .try_set_splitter_wasm_hash(&create_dummy_wasm_hash(&env))
+        .is_err());
+    assert_eq!(factory.splitter_wasm_hash(), wasm_hash);
+    assert_eq!(factory.latest_version(), 1);
+}
+
+#[test]
+fn test_create_with_version() {
+    let env = setup_test_env();
+    let (_factory_id, factory) = create_factory(&env);
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let token = create_token(&env);
+
+    factory.init(&admin, &get_splitter_wasm_hash(&env));
+    // Version 2 is not deployable here, so only version 1 can produce a splitter
+    factory.set_splitter_wasm_hash(&create_dummy_wasm_hash(&env));
+    assert_eq!(
+        factory.wasm_hash_for_version(&1),
+        get_splitter_wasm_hash(&env)
+    );
+    assert_eq!(
+        factory.wasm_hash_for_version(&2),
+        create_dummy_wasm_hash(&env)
+    );
+
+    let alice = Address::generate(&env);
+    let splitter_address = factory.create_with_version(
+        &creator,
+        &1,
+        &create_salt(&env, b"version_1"),
+        &token,
+        &vec![&env, alice.clone()],
+        &vec![&env, 1],
+        &SplitterOptions::default(),
+    );
+
+    assert_eq!(factory.splitter_version(&splitter_address), Some(1));
+    assert_eq!(factory.list_splitters(&0, &1).get_unchecked(0).version, 1);
+
+    // The deployed code exposes the crate version it was built from
+    let splitter = SimpleSplitterClient::new(&env, &splitter_address);
+    assert_eq!(
+        splitter.version(),
+        soroban_sdk::String::from_str(&env, "0.1.0")
+    );
+
+    // Unregistered versions are rejected
+    for version in [0, 3] {
+        assert_eq!(
+            factory.try_create_with_version(
+                &creator,
+                &version,
+                &create_salt(&env, b"unknown_version"),
+                &token,
+                &vec![&env, alice.clone()],
+                &vec![&env, 1],
+                &SplitterOptions::default(),
+            ),
+            Err(Ok(Error::UnknownVersion))
+        );
+    }
+    assert_eq!(
+        factory.try_wasm_hash_for_version(&3),
+        Err(Ok(Error::UnknownVersion))
+    );
+
+    // Addresses the factory did not deploy have no version
+    assert_eq!(factory.splitter_version(&alice), None);
+}
+
+#[test]
+fn test_create_uses_latest_version() {
+    let env = setup_test_env();
+    let (_factory_id, factory) = create_factory(&env);
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let token = create_token(&env);
+    let wasm_hash = get_splitter_wasm_hash(&env);
+
+    factory.init(&admin, &wasm_hash);
+    factory.set_splitter_wasm_hash(&create_dummy_wasm_hash(&env));
+    factory.set_splitter_wasm_hash(&wasm_hash);
+
+    let alice = Address::generate(&env);
+    let splitter_address = factory.create(
+        &creator,
+        &create_salt(&env, b"latest"),
+        &token,
+        &vec![&env, alice.clone()],
+        &vec![&env, 1],
+    );
+    assert_eq!(factory.splitter_version(&splitter_address), Some(3));
+}
+
+#[test]
+fn test_upgrade_factory_splitter_keeps_config() {
+    let env = setup_test_env();
+    let keeper = Address::generate(&env);
+    let (_factory_id, factory) = create_factory(&env);
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let token = create_token(&env);
+
+    factory.init(&admin, &get_splitter_wasm_hash(&env));
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let splitter_address = factory.create_with_options(
+        &creator,
+        &create_salt(&env, b"upgrade"),
+        &token,
+        &vec![&env, alice.clone(), bob.clone()],
+        &vec![&env, 3, 1],
+        &SplitterOptions {
+            owner: Some(owner.clone()),
+            payout_mode: PayoutMode::Claim,
+            remainder_policy: RemainderPolicy::FirstRecipient,
+            ..Default::default()
+        },
+    );
+    let splitter = SimpleSplitterClient::new(&env, &splitter_address);
+
+    // Leave an unclaimed balance behind to check it survives the upgrade
+    token::StellarAssetClient::new(&env, &token).mint(&splitter_address, &1000);
+    splitter.distribute(&keeper);
+    let config_before = splitter.get_config();
+    assert_eq!(splitter.storage_version(), 1);
+    let upgraded = upgraded_splitter_wasm::Client::new(&env, &splitter_address);
+    assert!(upgraded.try_upgraded().is_err());
+
+    // The owner rolls out the factory's latest registered code
+    let new_hash = env
+        .deployer()
+        .upload_contract_wasm(upgraded_splitter_wasm::WASM);
+    assert_eq!(factory.set_splitter_wasm_hash(&new_hash), 2);
+    splitter.upgrade(&factory.wasm_hash_for_version(&2));
+    assert_eq!(env.auths()[0].0, owner);
+
+    // The new code runs on top of the existing storage
+    assert!(upgraded.upgraded());
+    assert_eq!(splitter.migrate(), 1);
+    assert_eq!(splitter.storage_version(), 1);
+    assert_eq!(splitter.get_config(), config_before);
+    // The registry keeps the version the splitter was created from
+    assert_eq!(factory.splitter_version(&splitter_address), Some(1));
+    assert_eq!(splitter.claimable(&alice), 750);
+    assert_eq!(splitter.claimable(&bob), 250);
+    assert_eq!(splitter.claim(&alice), 750);
+}
+
+#[test]
+fn test_upgrade_immutable_factory_splitter_fails() {
+    let env = setup_test_env();
+    let (_factory_id, factory) = create_factory(&env);
+    let admin = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let token = create_token(&env);
+
+    factory.init(&admin, &get_splitter_wasm_hash(&env));
+
+    let alice = Address::generate(&env);
+    let splitter_address = factory.create(
+        &creator,
+        &create_salt(&env, b"immutable"),
+        &token,
+        &vec![&env, alice.clone()],
+        &vec![&env, 1],
+    );
+    let splitter = SimpleSplitterClient::new(&env, &splitter_address);
+
+    assert_eq!(
+        splitter.try_upgrade(&get_splitter_wasm_hash(&env)),
+        Err(Ok(simple_splitter::Error::Immutable))
+    );
+}
+
+#[test]
+fn test_create_with_duplicate_recipients_fails() {
+    let env = setup_test_env();
+    let (_factory_id, factory) = create_factory(&env);
+    let creator = Address::generate(&env);
+    let token = create_token(&env);
+
+    let admin = Address::generate(&env);
+    factory.init(&admin, &get_splitter_wasm_hash(&env));
+
+    let alice = Address::generate(&env);
+    assert_eq!(
+        factory.try_create(
+            &creator,
+            &create_salt(&env, b"duplicate"),
+            &token,
+            &vec![&env, alice.clone(), alice.clone()],
+            &vec![&env, 1, 1],
+        ),
+        Err(Ok(Error::DuplicateRecipient))
+    );
+}
+
+#[test]
+fn test_create_basis_points_splitter() {
+    let env = setup_test_env();
+    let (_factory_id, factory) = create_factory(&env);
+    let creator = Address::generate(&env);
+    let token = create_token(&env);
+
+    let admin = Address::generate(&env);
+    factory.init(&admin, &get_splitter_wasm_hash(&env));
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let options = SplitterOptions {
+        share_mode: ShareMode::BasisPoints,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        factory.try_create_with_options(
+            &creator,
+            &create_salt(&env, b"bps_invalid"),
+            &token,
+            &vec![&env, alice.clone(), bob.clone()],
+            &vec![&env, 3, 7],
+            &options,
+        ),
+        Err(Ok(Error::InvalidBasisPoints))
+    );
+
+    let splitter_address = factory.create_with_options(
+        &creator,
+        &create_salt(&env, b"bps"),
+        &token,
+        &vec![&env, alice.clone(), bob.clone()],
+        &vec![&env, 3_000, 7_000],
+        &options,
+    );
+
+    let config = SimpleSplitterClient::new(&env, &splitter_address).get_config();
+    assert_eq!(config.share_mode, simple_splitter::ShareMode::BasisPoints);
+    assert_eq!(config.percentages_bps, vec![&env, 3_000, 7_000]);
+}
+
+#[test]
+fn test_create_with_persistent_layout() {
+    let env = setup_test_env();
+    let (_factory_id, factory) = create_factory(&env);
+    let creator = Address::generate(&env);
+    let token = create_token(&env);
+
+    let admin = Address::generate(&env);
+    factory.init(&admin, &get_splitter_wasm_hash(&env));
+
+    // The splitter's first recipient page takes one write on top of the
+    // factory's index, so one recipient fewer than the instance layout fits
+    let count = simple_splitter::MAX_RECIPIENTS - 1;
+    let mut recipients = vec![&env];
+    let mut shares = vec![&env];
+    for _ in 0..count {
+        recipients.push_back(Address::generate(&env));
+        shares.push_back(1);
+    }
+
+    let splitter_address = factory.create_with_options(
+        &creator,
+        &create_salt(&env, b"persistent"),
+        &token,
+        &recipients,
+        &shares,
+        &SplitterOptions {
+            storage_layout: StorageLayout::Persistent,
+            ..Default::default()
+        },
+    );
+    let resources = env.cost_estimate().resources();
+    assert!(resources.write_entries <= 25, "{resources:?}");
+
+    let config = SimpleSplitterClient::new(&env, &splitter_address).get_config();
+    assert_eq!(
+        config.storage_layout,
+        simple_splitter::StorageLayout::Persistent
+    );
+    assert_eq!(config.recipients, recipients);
+    assert_eq!(
+        factory.splitters_for_recipient(&recipients.get_unchecked(count - 1), &0, &10),
+        vec![&env, splitter_address]
+    );
 }
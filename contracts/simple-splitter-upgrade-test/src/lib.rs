@@ -0,0 +1,21 @@
+#![no_std]
+
+//! SimpleSplitter plus one extra entrypoint. The factory tests upgrade
+//! deployed splitters to this WASM to check that genuinely new code takes over
+//! their existing storage.
+
+use soroban_sdk::{contract, contractimpl, Env};
+
+// Linking the splitter pulls in all of its entrypoints
+pub use simple_splitter::SimpleSplitter;
+
+#[contract]
+pub struct UpgradedSplitter;
+
+#[contractimpl]
+impl UpgradedSplitter {
+    /// Only exists in the upgraded code.
+    pub fn upgraded(_env: Env) -> bool {
+        true
+    }
+}
@@ -1,31 +1,288 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env, Vec};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, BytesN, Env,
+    String, Vec,
+};
 
 mod test;
 
 const ONE_YEAR_LEDGERS: u32 = 5_184_000;
 
+/// Layout version of this contract's storage. Bump it and extend `migrate`
+/// whenever a release changes how existing entries are stored.
+const STORAGE_VERSION: u32 = 1;
+
+/// Hard cap on recipients. A push-mode distribution transfers to every
+/// recipient in one transaction, writing one balance entry per recipient plus
+/// the splitter's own, which must stay within Soroban's 25 write entries per
+/// transaction.
+pub const MAX_RECIPIENTS: u32 = 20;
+
+/// Hard cap on recipients with `StorageLayout::Persistent`, small enough for
+/// `init`, `update_recipients`, `get_config` and the previews to handle the
+/// whole split in one transaction. Splits this large cannot be paid out in one
+/// transaction; use `distribute_batch`.
+pub const MAX_PERSISTENT_RECIPIENTS: u32 = 500;
+
+/// Recipients stored per persistent entry with `StorageLayout::Persistent`,
+/// so even the largest split is written and read in a handful of entries.
+const RECIPIENTS_PER_PAGE: u32 = 50;
+
+/// Ledgers a batched round may go without progress before anyone can cancel
+/// it (about a day at five seconds per ledger).
+pub const ROUND_TIMEOUT_LEDGERS: u32 = 17_280;
+
+/// Shares must sum to this in `ShareMode::BasisPoints` (100%).
+pub const TOTAL_BASIS_POINTS: u32 = 10_000;
+
+/// A keeper fee never exceeds this portion of the distributed balance (1%).
+pub const MAX_KEEPER_FEE_BPS: u32 = 100;
+
+/// Errors returned by SimpleSplitter entrypoints.
+/// Codes are part of the public interface and must not be renumbered.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    LengthMismatch = 3,
+    ZeroTotalShares = 4,
+    Reentrancy = 5,
+    Overflow = 6,
+    NegativeBalance = 7,
+    Immutable = 8,
+    NoPendingOwner = 9,
+    TokenNotAllowed = 10,
+    InvalidAmount = 11,
+    InsufficientBalance = 12,
+    Paused = 13,
+    Unauthorized = 14,
+    DuplicateRecipient = 15,
+    SelfRecipient = 16,
+    TooManyRecipients = 17,
+    InvalidBasisPoints = 18,
+    RoundInProgress = 19,
+    InvalidBatchSize = 20,
+    InvalidKeeperFee = 21,
+    BelowMinDistribution = 22,
+    DistributionTooSoon = 23,
+    NoRoundInProgress = 24,
+}
+
+/// How `distribute` hands out each recipient's share.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum PayoutMode {
+    /// Transfer shares directly to recipients. One failing transfer reverts the whole payout.
+    #[default]
+    Push,
+    /// Credit shares to per-recipient balances that recipients withdraw with `claim`.
+    Claim,
+}
+
+/// What happens to the integer-division leftover of each distribution.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum RemainderPolicy {
+    /// Leave the leftover in the contract; it rolls into the next distribution.
+    #[default]
+    Keep,
+    /// Add the leftover to the first recipient with a non-zero share.
+    FirstRecipient,
+    /// Add the leftover to the recipient with the most shares (earliest on ties).
+    LargestShareholder,
+    /// Add the leftover to each recipient with a non-zero share in turn,
+    /// advancing once per distribution that has a leftover.
+    RoundRobin,
+}
+
+/// How the `shares` vector is interpreted.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ShareMode {
+    /// Shares are arbitrary ratios; each recipient gets `share / total`.
+    #[default]
+    Ratio,
+    /// Shares are basis points and must sum to exactly `TOTAL_BASIS_POINTS`.
+    BasisPoints,
+}
+
+/// Where the recipients and shares are stored.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum StorageLayout {
+    /// Both vectors live in instance storage, loaded on every invocation.
+    /// Cheapest for small splits.
+    #[default]
+    Instance,
+    /// Recipients and shares live in persistent entries of up to 50 each, read
+    /// only when (and as far as) the split is needed. Allows up to
+    /// `MAX_PERSISTENT_RECIPIENTS`.
+    Persistent,
+}
+
+/// Reward paid to whoever calls `distribute`, taken from the balance before
+/// it is split. Always capped at `MAX_KEEPER_FEE_BPS` of that balance.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum KeeperFee {
+    #[default]
+    None,
+    /// A fixed amount of the primary token per distribution.
+    Fixed(i128),
+    /// A portion of the distributed balance in basis points.
+    BasisPoints(u32),
+}
+
+/// Optional settings accepted by `init_with_options`.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SplitterOptions {
+    /// Address allowed to update recipients and shares.
+    /// `None` keeps the split immutable.
+    pub owner: Option<Address>,
+    /// Address allowed to pause and unpause payouts alongside the owner.
+    pub guardian: Option<Address>,
+    pub payout_mode: PayoutMode,
+    /// Extra tokens `distribute_token` may split besides the primary token.
+    /// `None` allows any token held by the contract.
+    pub allowed_tokens: Option<Vec<Address>>,
+    pub remainder_policy: RemainderPolicy,
+    /// Upper bound on recipients for init and every later update, clamped to
+    /// the storage layout's cap. `None` uses the cap.
+    pub max_recipients: Option<u32>,
+    pub share_mode: ShareMode,
+    pub storage_layout: StorageLayout,
+    pub keeper_fee: KeeperFee,
+    /// Smallest amount of the primary token `distribute`, `distribute_token`,
+    /// `distribute_amount` and a new `distribute_batch` round will split.
+    /// Zero disables the check.
+    pub min_distribution: i128,
+    /// Ledgers that must pass after a distribution before the next one may
+    /// start. Zero disables the limit.
+    pub min_ledger_gap: u32,
+}
+
+/// Persistent and per-token storage keys.
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    /// Amount of `token` credited to `recipient` and not yet claimed.
+    Claimable(Address, Address),
+    /// Total amount of `token` credited to recipients and not yet claimed.
+    Owed(Address),
+    /// Amount of `token` left undistributed by the last distribution.
+    Dust(Address),
+    /// Ledger sequence of the last distribution of `token` that paid anything.
+    LastDistribution(Address),
+    /// Recipients and shares of one page with `StorageLayout::Persistent`:
+    /// indices from `page * RECIPIENTS_PER_PAGE`, at most `RECIPIENTS_PER_PAGE`.
+    RecipientPage(u32),
+}
+
+/// Snapshot of a splitter's configuration returned by `get_config`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Config {
+    pub token: Address,
+    pub recipients: Vec<Address>,
+    pub shares: Vec<u32>,
+    pub owner: Option<Address>,
+    /// True when there is no owner, so the split can never change.
+    pub immutable: bool,
+    pub payout_mode: PayoutMode,
+    pub allowed_tokens: Option<Vec<Address>>,
+    pub remainder_policy: RemainderPolicy,
+    pub guardian: Option<Address>,
+    /// True while distributions and claims are halted.
+    pub paused: bool,
+    /// Most recipients `update_recipients` will accept.
+    pub max_recipients: u32,
+    pub share_mode: ShareMode,
+    pub storage_layout: StorageLayout,
+    pub keeper_fee: KeeperFee,
+    pub min_distribution: i128,
+    pub min_ledger_gap: u32,
+    /// Each recipient's portion in basis points (rounded down), whatever the mode.
+    pub percentages_bps: Vec<u32>,
+}
+
+/// State of a batched distribution round, returned by `distribution_progress`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DistributionProgress {
+    /// True between the first and the last `distribute_batch` call of a round.
+    pub in_progress: bool,
+    /// Balance snapshot taken when the round started.
+    pub amount: i128,
+    /// Amount paid out so far in the round.
+    pub distributed: i128,
+    /// Index of the next recipient to pay.
+    pub next_index: u32,
+    pub recipient_count: u32,
+}
+
 #[contract]
 pub struct SimpleSplitter;
 
-/// Stores recipients and share ratios as immutable config.
+/// Stores recipients and share ratios as config, mutable only by an optional owner.
 #[contractimpl]
 impl SimpleSplitter {
-    pub fn init(env: Env, token: Address, recipients: Vec<Address>, shares: Vec<u32>) {
+    /// Initialize an immutable splitter with no owner.
+    pub fn init(
+        env: Env,
+        token: Address,
+        recipients: Vec<Address>,
+        shares: Vec<u32>,
+    ) -> Result<(), Error> {
+        Self::init_with_options(env, token, recipients, shares, SplitterOptions::default())
+    }
+
+    /// Initialize a splitter with optional settings such as an owner.
+    pub fn init_with_options(
+        env: Env,
+        token: Address,
+        recipients: Vec<Address>,
+        shares: Vec<u32>,
+        options: SplitterOptions,
+    ) -> Result<(), Error> {
         // Prevent reinitialization
         if env.storage().instance().has(&symbol_short!("initd")) {
-            panic!("already initialized");
+            return Err(Error::AlreadyInitialized);
         }
 
-        assert!(recipients.len() == shares.len(), "length mismatch");
-
-        // Validate that at least one share is non-zero to prevent division by zero
-        let total: u128 = shares.iter().map(|s| s as u128).sum();
-        assert!(total > 0, "total shares must be greater than zero");
+        let layout_cap = match options.storage_layout {
+            StorageLayout::Instance => MAX_RECIPIENTS,
+            StorageLayout::Persistent => MAX_PERSISTENT_RECIPIENTS,
+        };
+        let max_recipients = options
+            .max_recipients
+            .map_or(layout_cap, |max| max.min(layout_cap));
+        validate_split(
+            &env,
+            &recipients,
+            &shares,
+            max_recipients,
+            options.share_mode,
+        )?;
+        match options.keeper_fee {
+            KeeperFee::Fixed(fee) if fee < 0 => return Err(Error::InvalidKeeperFee),
+            KeeperFee::BasisPoints(bps) if bps > MAX_KEEPER_FEE_BPS => {
+                return Err(Error::InvalidKeeperFee)
+            }
+            _ => {}
+        }
+        if options.min_distribution < 0 {
+            return Err(Error::InvalidAmount);
+        }
 
         // Mark as initialized
         env.storage().instance().set(&symbol_short!("initd"), &true);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("schema"), &STORAGE_VERSION);
 
         // persist token, recipients, shares in env storage
         env.storage()
@@ -33,102 +290,1140 @@ impl SimpleSplitter {
             .set(&symbol_short!("token"), &token);
         env.storage()
             .instance()
-            .set(&symbol_short!("recips"), &recipients);
+            .set(&symbol_short!("layout"), &options.storage_layout);
+        store_split(&env, &recipients, &shares);
+        if let Some(owner) = options.owner {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("owner"), &owner);
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("mode"), &options.payout_mode);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("policy"), &options.remainder_policy);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("maxrecip"), &max_recipients);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("sharemode"), &options.share_mode);
         env.storage()
             .instance()
-            .set(&symbol_short!("shares"), &shares);
+            .set(&symbol_short!("keeperfee"), &options.keeper_fee);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("mindist"), &options.min_distribution);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("ledgergap"), &options.min_ledger_gap);
+        if let Some(guardian) = options.guardian {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("guardian"), &guardian);
+        }
+        if let Some(allowed_tokens) = options.allowed_tokens {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("allowed"), &allowed_tokens);
+        }
         env.storage()
             .instance()
             .extend_ttl(ONE_YEAR_LEDGERS, ONE_YEAR_LEDGERS);
+
+        Ok(())
     }
 
-    pub fn distribute(env: Env) {
-        // Reentrancy guard: check if already executing
-        if env
-            .storage()
+    /// Replace recipients and shares. Only callable by the owner, and validated
+    /// exactly like `init`. Publishes an `("updated",)` event with the new split.
+    pub fn update_recipients(
+        env: Env,
+        recipients: Vec<Address>,
+        shares: Vec<u32>,
+    ) -> Result<(), Error> {
+        let owner = Self::owner(env.clone())?.ok_or(Error::Immutable)?;
+        owner.require_auth();
+        ensure_no_round(&env)?;
+
+        validate_split(
+            &env,
+            &recipients,
+            &shares,
+            max_recipients(&env),
+            share_mode(&env),
+        )?;
+
+        store_split(&env, &recipients, &shares);
+
+        env.events()
+            .publish((symbol_short!("updated"),), (recipients, shares));
+
+        env.storage()
             .instance()
-            .get::<_, bool>(&symbol_short!("lock"))
-            .unwrap_or(false)
-        {
-            panic!("reentrancy detected");
+            .extend_ttl(ONE_YEAR_LEDGERS, ONE_YEAR_LEDGERS);
+
+        Ok(())
+    }
+
+    /// Current owner, or `None` if the splitter is immutable.
+    pub fn owner(env: Env) -> Result<Option<Address>, Error> {
+        if !env.storage().instance().has(&symbol_short!("initd")) {
+            return Err(Error::NotInitialized);
         }
+        Ok(env.storage().instance().get(&symbol_short!("owner")))
+    }
 
-        // Set reentrancy lock
-        env.storage().instance().set(&symbol_short!("lock"), &true);
+    /// Address proposed as the next owner, if any.
+    pub fn pending_owner(env: Env) -> Option<Address> {
+        env.storage().instance().get(&symbol_short!("pending"))
+    }
 
-        let token: Address = env
-            .storage()
+    /// True when the splitter has no owner, either because none was set at
+    /// init or because ownership was renounced.
+    pub fn is_immutable(env: Env) -> Result<bool, Error> {
+        Ok(Self::owner(env)?.is_none())
+    }
+
+    /// Propose a new owner. Takes effect once `new_owner` calls `accept_owner`.
+    pub fn propose_owner(env: Env, new_owner: Address) -> Result<(), Error> {
+        let owner = Self::owner(env.clone())?.ok_or(Error::Immutable)?;
+        owner.require_auth();
+
+        env.storage()
             .instance()
-            .get(&symbol_short!("token"))
-            .unwrap();
-        let recipients: Vec<Address> = env
-            .storage()
+            .set(&symbol_short!("pending"), &new_owner);
+        env.events()
+            .publish((symbol_short!("proposed"),), (owner, new_owner));
+
+        Ok(())
+    }
+
+    /// Complete an ownership transfer. Must be authorized by the proposed owner.
+    pub fn accept_owner(env: Env) -> Result<(), Error> {
+        let previous = Self::owner(env.clone())?.ok_or(Error::Immutable)?;
+        let new_owner = Self::pending_owner(env.clone()).ok_or(Error::NoPendingOwner)?;
+        new_owner.require_auth();
+
+        env.storage()
             .instance()
-            .get(&symbol_short!("recips"))
-            .unwrap();
-        let shares: Vec<u32> = env
-            .storage()
+            .set(&symbol_short!("owner"), &new_owner);
+        env.storage().instance().remove(&symbol_short!("pending"));
+        env.events()
+            .publish((symbol_short!("accepted"),), (previous, new_owner));
+
+        env.storage()
             .instance()
-            .get(&symbol_short!("shares"))
-            .unwrap();
+            .extend_ttl(ONE_YEAR_LEDGERS, ONE_YEAR_LEDGERS);
 
-        let sac = soroban_sdk::token::Client::new(&env, &token);
+        Ok(())
+    }
 
-        let balance = sac.balance(&env.current_contract_address());
+    /// Permanently remove the owner, freezing recipients and shares.
+    pub fn renounce_ownership(env: Env) -> Result<(), Error> {
+        let owner = Self::owner(env.clone())?.ok_or(Error::Immutable)?;
+        owner.require_auth();
 
-        // Ensure balance is non-negative (should always be true for tokens, but be safe)
-        if balance < 0 {
-            panic!("negative balance");
+        env.storage().instance().remove(&symbol_short!("owner"));
+        env.storage().instance().remove(&symbol_short!("pending"));
+        env.events().publish((symbol_short!("renounced"),), owner);
+
+        Ok(())
+    }
+
+    /// Replace this contract's code with `new_wasm_hash`, keeping its storage.
+    /// Only callable by the owner; immutable splitters cannot be upgraded.
+    /// Call `migrate` afterwards so storage matches the new code.
+    /// Publishes an `("upgraded",)` event with the new hash. A factory's
+    /// `splitter_version` is not updated; `version()` reports the new code.
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        let owner = Self::owner(env.clone())?.ok_or(Error::Immutable)?;
+        owner.require_auth();
+
+        env.deployer()
+            .update_current_contract_wasm(new_wasm_hash.clone());
+        env.events()
+            .publish((symbol_short!("upgraded"),), new_wasm_hash);
+
+        Ok(())
+    }
+
+    /// Bring storage written by an older release up to `STORAGE_VERSION`.
+    /// Safe to call repeatedly; returns the storage version afterwards.
+    pub fn migrate(env: Env) -> Result<u32, Error> {
+        if !env.storage().instance().has(&symbol_short!("initd")) {
+            return Err(Error::NotInitialized);
+        }
+
+        let stored = Self::storage_version(env.clone());
+        if stored < STORAGE_VERSION {
+            // Version 0 (no "schema" entry) has the same layout as version 1,
+            // so there is nothing to rewrite yet.
+            env.storage()
+                .instance()
+                .set(&symbol_short!("schema"), &STORAGE_VERSION);
+        }
+        env.storage()
+            .instance()
+            .extend_ttl(ONE_YEAR_LEDGERS, ONE_YEAR_LEDGERS);
+
+        Ok(Self::storage_version(env))
+    }
+
+    /// Layout version of the stored data (0 for splitters created before
+    /// versioning was introduced).
+    pub fn storage_version(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("schema"))
+            .unwrap_or(0)
+    }
+
+    /// Halt distributions and claims. `caller` must be the owner or the guardian.
+    /// Publishes a `("paused",)` event with the caller.
+    pub fn pause(env: Env, caller: Address) -> Result<(), Error> {
+        set_paused(&env, caller, true)
+    }
+
+    /// Resume distributions and claims. `caller` must be the owner or the guardian.
+    /// Publishes an `("unpaused",)` event with the caller.
+    pub fn unpause(env: Env, caller: Address) -> Result<(), Error> {
+        set_paused(&env, caller, false)
+    }
+
+    pub fn is_paused(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("paused"))
+            .unwrap_or(false)
+    }
+
+    pub fn guardian(env: Env) -> Option<Address> {
+        env.storage().instance().get(&symbol_short!("guardian"))
+    }
+
+    /// Splits the contract's full balance of the primary token among recipients.
+    /// `caller` must authorize the call and receives the keeper fee configured
+    /// at init, if any, with a `("keeper", token, caller)` event carrying the fee.
+    /// Fails with `BelowMinDistribution` when the balance is under the
+    /// `min_distribution` set at init, and with `DistributionTooSoon` until
+    /// `min_ledger_gap` ledgers have passed since the last distribution of the
+    /// primary token.
+    pub fn distribute(env: Env, caller: Address) -> Result<(), Error> {
+        caller.require_auth();
+
+        acquire_lock(&env)?;
+        ensure_not_paused(&env)?;
+        ensure_no_round(&env)?;
+
+        let token = primary_token(&env)?;
+        ensure_ledger_gap(&env, &token)?;
+        let balance = available_balance(&env, &token)?;
+        ensure_min_distribution(&env, balance)?;
+
+        let fee = keeper_fee_for(&env, balance)?;
+        if fee > 0 {
+            let sac = soroban_sdk::token::Client::new(&env, &token);
+            sac.transfer(&env.current_contract_address(), &caller, &fee);
+            env.events()
+                .publish((symbol_short!("keeper"), token.clone(), caller), fee);
         }
 
-        let balance_u128 = balance as u128;
-        let total: u128 = shares.iter().map(|s| s as u128).sum();
+        split_and_pay(&env, &token, balance - fee)?;
+
+        release_lock(&env);
+        Ok(())
+    }
+
+    /// Splits the contract's full balance of `token` among recipients using the
+    /// same share table. `token` must be the primary token or, when an allowlist
+    /// was configured at init, one of the allowed tokens.
+    ///
+    /// Publishes a `("payout", token, recipient)` event with the amount for every
+    /// transfer, followed by a `("distrib", token)` summary event carrying
+    /// `(total_distributed, remainder, ledger_sequence)`. The integer-division
+    /// leftover is handled by the remainder policy set at init.
+    ///
+    /// In `PayoutMode::Claim` shares are credited instead of transferred, with a
+    /// `("credit", token, recipient)` event each, and funds already credited but
+    /// not yet claimed are excluded from the balance being split.
+    ///
+    /// For the primary token, `min_distribution` applies as in `distribute`.
+    /// `min_ledger_gap` is counted separately for each token.
+    pub fn distribute_token(env: Env, token: Address) -> Result<(), Error> {
+        acquire_lock(&env)?;
+        ensure_not_paused(&env)?;
+        ensure_ledger_gap(&env, &token)?;
 
-        for (i, r) in recipients.iter().enumerate() {
-            let share_ratio = shares.get(i as u32).unwrap() as u128;
+        if !is_token_allowed(&env, &token)? {
+            return Err(Error::TokenNotAllowed);
+        }
 
-            // Use checked arithmetic to prevent overflow
-            let share = balance_u128
-                .checked_mul(share_ratio)
-                .expect("overflow in share calculation")
-                .checked_div(total)
-                .expect("division error"); // total > 0 guaranteed by init validation
+        let balance = available_balance(&env, &token)?;
+        if token == primary_token(&env)? {
+            ensure_no_round(&env)?;
+            ensure_min_distribution(&env, balance)?;
+        }
+        split_and_pay(&env, &token, balance)?;
+
+        release_lock(&env);
+        Ok(())
+    }
+
+    /// Splits only `amount` of the primary token using the same share ratios,
+    /// leaving the rest of the balance in the contract as a reserve.
+    /// `amount` must be positive.
+    pub fn distribute_amount(env: Env, amount: i128) -> Result<(), Error> {
+        acquire_lock(&env)?;
+        ensure_not_paused(&env)?;
+        ensure_no_round(&env)?;
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
 
-            if share > 0 {
-                // Ensure share fits in i128
-                let share_i128 = i128::try_from(share).expect("share too large for i128");
-                sac.transfer(&env.current_contract_address(), &r, &share_i128);
+        let token = primary_token(&env)?;
+        ensure_ledger_gap(&env, &token)?;
+        if amount > available_balance(&env, &token)? {
+            return Err(Error::InsufficientBalance);
+        }
+        ensure_min_distribution(&env, amount)?;
+        split_and_pay(&env, &token, amount)?;
+
+        release_lock(&env);
+        Ok(())
+    }
+
+    /// Pay the next `limit` recipients of a distribution round of the primary
+    /// token, so splits too large for one transaction can still be paid out.
+    ///
+    /// The first call snapshots the distributable balance; later calls resume
+    /// from a stored cursor until every recipient has been paid. Deposits made
+    /// mid-round wait for the next round. The integer-division leftover is
+    /// handled by the remainder policy on the last batch, which also publishes
+    /// the `("distrib", token)` summary event. Other distributions of the
+    /// primary token and `update_recipients` fail with `RoundInProgress` until
+    /// the round ends.
+    pub fn distribute_batch(env: Env, limit: u32) -> Result<DistributionProgress, Error> {
+        acquire_lock(&env)?;
+        ensure_not_paused(&env)?;
+
+        if limit == 0 {
+            return Err(Error::InvalidBatchSize);
+        }
+
+        let token = primary_token(&env)?;
+        let mut round = match env.storage().instance().get(&symbol_short!("round")) {
+            Some(round) => round,
+            None => {
+                ensure_ledger_gap(&env, &token)?;
+                let amount = available_balance(&env, &token)?;
+                ensure_min_distribution(&env, amount)?;
+                DistributionProgress {
+                    in_progress: true,
+                    amount,
+                    distributed: 0,
+                    next_index: 0,
+                    recipient_count: recipient_count(&env)?,
+                }
+            }
+        };
+
+        let (total, largest) = split_summary(&env)?;
+        let end = round
+            .next_index
+            .saturating_add(limit)
+            .min(round.recipient_count);
+        let (recipients, shares) = load_range(&env, round.next_index, end)?;
+        let payouts = proportional_payouts(&env, &shares, total, round.amount)?;
+        round.distributed += pay_out(&env, &token, &recipients, &payouts);
+        round.next_index = end;
+
+        if round.next_index < round.recipient_count {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("round"), &round);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("roundat"), &env.ledger().sequence());
+            release_lock(&env);
+            return Ok(round);
+        }
+
+        // Last batch: hand out the leftover and close the round
+        let mut remainder_to = None;
+        let remainder = round.amount - round.distributed;
+        if remainder > 0 {
+            remainder_to = remainder_index(&env, round.recipient_count, largest, |i| {
+                Ok(load_range(&env, i, i + 1)?.1.get_unchecked(0))
+            })?;
+            if let Some(index) = remainder_to {
+                let (recipient, _) = load_range(&env, index, index + 1)?;
+                let mut payout = Vec::new(&env);
+                payout.push_back(remainder);
+                round.distributed += pay_out(&env, &token, &recipient, &payout);
             }
         }
+        finish_distribution(&env, &token, round.amount, round.distributed, remainder_to);
+
+        env.storage().instance().remove(&symbol_short!("round"));
+        env.storage().instance().remove(&symbol_short!("roundat"));
+        round.in_progress = false;
 
-        // Release reentrancy lock
-        env.storage().instance().set(&symbol_short!("lock"), &false);
+        release_lock(&env);
+        Ok(round)
+    }
+
+    /// Progress of the current batched distribution round, if any.
+    pub fn distribution_progress(env: Env) -> Result<DistributionProgress, Error> {
+        match env.storage().instance().get(&symbol_short!("round")) {
+            Some(round) => Ok(round),
+            None => Ok(DistributionProgress {
+                in_progress: false,
+                amount: 0,
+                distributed: 0,
+                next_index: 0,
+                recipient_count: recipient_count(&env)?,
+            }),
+        }
+    }
+
+    /// Abandon the current batched round, e.g. when a recipient further along
+    /// can no longer receive transfers and every later batch would fail. The
+    /// owner or guardian may cancel at any time; anyone else once
+    /// `ROUND_TIMEOUT_LEDGERS` have passed since the round last made progress,
+    /// so a round on an immutable splitter cannot stay stuck forever.
+    /// Recipients already paid keep their payouts; the unpaid rest of the
+    /// snapshot stays in the contract and is split by the next distribution.
+    /// Publishes a `("cancelled", caller)` event with the abandoned progress.
+    pub fn cancel_round(env: Env, caller: Address) -> Result<DistributionProgress, Error> {
+        caller.require_auth();
+
+        let mut round: DistributionProgress = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("round"))
+            .ok_or(Error::NoRoundInProgress)?;
+
+        let owner = Self::owner(env.clone())?;
+        let guardian = Self::guardian(env.clone());
+        if owner.as_ref() != Some(&caller) && guardian.as_ref() != Some(&caller) {
+            let last_progress: u32 = env
+                .storage()
+                .instance()
+                .get(&symbol_short!("roundat"))
+                .unwrap_or(0);
+            if env.ledger().sequence() < last_progress.saturating_add(ROUND_TIMEOUT_LEDGERS) {
+                return Err(Error::Unauthorized);
+            }
+        }
+
+        env.storage().instance().remove(&symbol_short!("round"));
+        env.storage().instance().remove(&symbol_short!("roundat"));
+        round.in_progress = false;
+
+        env.events()
+            .publish((symbol_short!("cancelled"), caller), round.clone());
+
+        Ok(round)
+    }
+
+    /// Withdraw everything credited to `recipient` in claim mode.
+    /// Returns the amount transferred, which is zero if nothing was owed.
+    pub fn claim(env: Env, recipient: Address) -> Result<i128, Error> {
+        let token = primary_token(&env)?;
+        Self::claim_token(env, recipient, token)
+    }
+
+    /// Withdraw everything of `token` credited to `recipient` in claim mode.
+    pub fn claim_token(env: Env, recipient: Address, token: Address) -> Result<i128, Error> {
+        recipient.require_auth();
+
+        if !env.storage().instance().has(&symbol_short!("initd")) {
+            return Err(Error::NotInitialized);
+        }
+        ensure_not_paused(&env)?;
+
+        let key = DataKey::Claimable(token.clone(), recipient.clone());
+        let amount: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        if amount == 0 {
+            return Ok(0);
+        }
+
+        // Clear the credit before transferring
+        env.storage().persistent().remove(&key);
+        env.storage().instance().set(
+            &DataKey::Owed(token.clone()),
+            &(owed(&env, &token) - amount),
+        );
+
+        let sac = soroban_sdk::token::Client::new(&env, &token);
+        sac.transfer(&env.current_contract_address(), &recipient, &amount);
+
+        env.events()
+            .publish((symbol_short!("claim"), token, recipient), amount);
 
-        // Extend TTL to keep contract alive
         env.storage()
             .instance()
             .extend_ttl(ONE_YEAR_LEDGERS, ONE_YEAR_LEDGERS);
+
+        Ok(amount)
     }
 
-    pub fn get_config(env: Env) -> (Address, Vec<Address>, Vec<u32>) {
+    /// Amount currently credited to `recipient` and waiting to be claimed.
+    pub fn claimable(env: Env, recipient: Address) -> Result<i128, Error> {
+        let token = primary_token(&env)?;
+        Ok(Self::claimable_token(env, recipient, token))
+    }
+
+    /// Amount of `token` currently credited to `recipient`.
+    pub fn claimable_token(env: Env, recipient: Address, token: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Claimable(token, recipient))
+            .unwrap_or(0)
+    }
+
+    /// What each recipient would receive if `distribute` ran now, in recipient
+    /// order. Uses the same math and remainder policy as `distribute`, after
+    /// taking out the keeper fee.
+    pub fn preview_distribution(env: Env) -> Result<Vec<(Address, i128)>, Error> {
+        let token = primary_token(&env)?;
+        let balance = available_balance(&env, &token)?;
+        let amount = balance - keeper_fee_for(&env, balance)?;
+        Self::preview_for_amount(env, amount)
+    }
+
+    /// What each recipient would receive if `amount` were distributed.
+    pub fn preview_for_amount(env: Env, amount: i128) -> Result<Vec<(Address, i128)>, Error> {
+        if amount < 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let (recipients, shares) = load_split(&env)?;
+        let (payouts, _) = split_amount(&env, &shares, amount)?;
+
+        let mut preview = Vec::new(&env);
+        for (recipient, payout) in recipients.iter().zip(payouts.iter()) {
+            preview.push_back((recipient, payout));
+        }
+        Ok(preview)
+    }
+
+    /// First ledger sequence at which `token` may be distributed again, given
+    /// the `min_ledger_gap` set at init. A value at or below the current
+    /// sequence means a distribution is allowed now.
+    pub fn next_distribution_ledger(env: Env, token: Address) -> u32 {
+        next_distribution_ledger(&env, &token)
+    }
+
+    /// Amount of `token` the last distribution left in the contract because of
+    /// integer division. Always zero unless the remainder policy is `Keep`.
+    pub fn dust(env: Env, token: Address) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::Dust(token))
+            .unwrap_or(0)
+    }
+
+    pub fn get_config(env: Env) -> Result<Config, Error> {
         // Extend TTL when reading config
         env.storage()
             .instance()
             .extend_ttl(ONE_YEAR_LEDGERS, ONE_YEAR_LEDGERS);
 
-        (
-            env.storage()
-                .instance()
-                .get(&symbol_short!("token"))
-                .unwrap(),
-            env.storage()
+        let owner = Self::owner(env.clone())?;
+        let (recipients, shares) = load_split(&env)?;
+        let percentages_bps = percentages_bps(&env, &shares);
+
+        Ok(Config {
+            token: primary_token(&env)?,
+            recipients,
+            shares,
+            immutable: owner.is_none(),
+            owner,
+            payout_mode: env
+                .storage()
                 .instance()
-                .get(&symbol_short!("recips"))
-                .unwrap(),
+                .get(&symbol_short!("mode"))
+                .unwrap_or_default(),
+            allowed_tokens: env.storage().instance().get(&symbol_short!("allowed")),
+            remainder_policy: remainder_policy(&env),
+            guardian: Self::guardian(env.clone()),
+            paused: Self::is_paused(env.clone()),
+            max_recipients: max_recipients(&env),
+            share_mode: share_mode(&env),
+            storage_layout: storage_layout(&env),
+            keeper_fee: keeper_fee(&env),
+            min_distribution: min_distribution(&env),
+            min_ledger_gap: min_ledger_gap(&env),
+            percentages_bps,
+        })
+    }
+
+    /// Version of the SimpleSplitter crate this contract was built from.
+    pub fn version(env: Env) -> String {
+        String::from_str(&env, env!("CARGO_PKG_VERSION"))
+    }
+}
+
+/// The token configured at init.
+fn primary_token(env: &Env) -> Result<Address, Error> {
+    env.storage()
+        .instance()
+        .get(&symbol_short!("token"))
+        .ok_or(Error::NotInitialized)
+}
+
+/// The primary token is always allowed; others only if no allowlist is set
+/// or they appear in it.
+fn is_token_allowed(env: &Env, token: &Address) -> Result<bool, Error> {
+    if *token == primary_token(env)? {
+        return Ok(true);
+    }
+    let allowed: Option<Vec<Address>> = env.storage().instance().get(&symbol_short!("allowed"));
+    Ok(allowed.is_none_or(|tokens| tokens.contains(token)))
+}
+
+/// Shared body of `pause` and `unpause`.
+fn set_paused(env: &Env, caller: Address, paused: bool) -> Result<(), Error> {
+    let owner = SimpleSplitter::owner(env.clone())?;
+    let guardian = SimpleSplitter::guardian(env.clone());
+    if owner.as_ref() != Some(&caller) && guardian.as_ref() != Some(&caller) {
+        return Err(Error::Unauthorized);
+    }
+    caller.require_auth();
+
+    env.storage()
+        .instance()
+        .set(&symbol_short!("paused"), &paused);
+
+    let topic = if paused {
+        symbol_short!("paused")
+    } else {
+        symbol_short!("unpaused")
+    };
+    env.events().publish((topic,), caller);
+
+    Ok(())
+}
+
+/// Keeper fee configured at init; `KeeperFee::None` for splitters created
+/// before keeper fees.
+fn keeper_fee(env: &Env) -> KeeperFee {
+    env.storage()
+        .instance()
+        .get(&symbol_short!("keeperfee"))
+        .unwrap_or_default()
+}
+
+/// Keeper reward for distributing `balance`, capped at `MAX_KEEPER_FEE_BPS`.
+fn keeper_fee_for(env: &Env, balance: i128) -> Result<i128, Error> {
+    let bps_of = |bps: u32| {
+        balance
+            .checked_mul(bps as i128)
+            .map(|scaled| scaled / TOTAL_BASIS_POINTS as i128)
+            .ok_or(Error::Overflow)
+    };
+
+    let fee = match keeper_fee(env) {
+        KeeperFee::None => 0,
+        KeeperFee::Fixed(fee) => fee,
+        KeeperFee::BasisPoints(bps) => bps_of(bps)?,
+    };
+    Ok(fee.min(bps_of(MAX_KEEPER_FEE_BPS)?))
+}
+
+/// Smallest primary-token amount a distribution may split; zero (no minimum)
+/// for splitters created before the threshold existed.
+fn min_distribution(env: &Env) -> i128 {
+    env.storage()
+        .instance()
+        .get(&symbol_short!("mindist"))
+        .unwrap_or(0)
+}
+
+/// Fails with `BelowMinDistribution` when `amount` is under the threshold.
+fn ensure_min_distribution(env: &Env, amount: i128) -> Result<(), Error> {
+    if amount < min_distribution(env) {
+        return Err(Error::BelowMinDistribution);
+    }
+    Ok(())
+}
+
+/// Ledgers that must pass between distributions; zero (no limit) for
+/// splitters created before rate limiting.
+fn min_ledger_gap(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&symbol_short!("ledgergap"))
+        .unwrap_or(0)
+}
+
+/// First ledger sequence at which a new distribution of `token` may start.
+fn next_distribution_ledger(env: &Env, token: &Address) -> u32 {
+    let last: Option<u32> = env
+        .storage()
+        .instance()
+        .get(&DataKey::LastDistribution(token.clone()));
+    last.map_or(0, |last| last.saturating_add(min_ledger_gap(env)))
+}
+
+/// Fails with `DistributionTooSoon` until `min_ledger_gap` ledgers have
+/// passed since the last distribution of `token`.
+fn ensure_ledger_gap(env: &Env, token: &Address) -> Result<(), Error> {
+    if env.ledger().sequence() < next_distribution_ledger(env, token) {
+        return Err(Error::DistributionTooSoon);
+    }
+    Ok(())
+}
+
+/// Fails with `RoundInProgress` while a batched round is unfinished.
+fn ensure_no_round(env: &Env) -> Result<(), Error> {
+    if env.storage().instance().has(&symbol_short!("round")) {
+        return Err(Error::RoundInProgress);
+    }
+    Ok(())
+}
+
+/// Fails with `Paused` while payouts are halted.
+fn ensure_not_paused(env: &Env) -> Result<(), Error> {
+    if SimpleSplitter::is_paused(env.clone()) {
+        return Err(Error::Paused);
+    }
+    Ok(())
+}
+
+/// Reentrancy guard: fails if a distribution is already executing.
+fn acquire_lock(env: &Env) -> Result<(), Error> {
+    if env
+        .storage()
+        .instance()
+        .get::<_, bool>(&symbol_short!("lock"))
+        .unwrap_or(false)
+    {
+        return Err(Error::Reentrancy);
+    }
+
+    env.storage().instance().set(&symbol_short!("lock"), &true);
+    Ok(())
+}
+
+/// Releases the reentrancy lock and extends the TTL to keep the contract alive.
+fn release_lock(env: &Env) {
+    env.storage().instance().set(&symbol_short!("lock"), &false);
+    env.storage()
+        .instance()
+        .extend_ttl(ONE_YEAR_LEDGERS, ONE_YEAR_LEDGERS);
+}
+
+/// Splits `amount` of `token` among recipients and pays or credits each share
+/// according to the payout mode, publishing the payout and summary events.
+fn split_and_pay(env: &Env, token: &Address, amount: i128) -> Result<(), Error> {
+    let (recipients, shares) = load_split(env)?;
+    let (payouts, remainder_to) = split_amount(env, &shares, amount)?;
+    let distributed = pay_out(env, token, &recipients, &payouts);
+    finish_distribution(env, token, amount, distributed, remainder_to);
+    Ok(())
+}
+
+/// Transfer (push mode) or credit (claim mode) each non-zero payout.
+/// Returns the total paid out.
+fn pay_out(env: &Env, token: &Address, recipients: &Vec<Address>, payouts: &Vec<i128>) -> i128 {
+    let mode: PayoutMode = env
+        .storage()
+        .instance()
+        .get(&symbol_short!("mode"))
+        .unwrap_or_default();
+
+    let sac = soroban_sdk::token::Client::new(env, token);
+    let mut distributed: i128 = 0;
+
+    for (r, share) in recipients.iter().zip(payouts.iter()) {
+        if share > 0 {
+            distributed += share;
+
+            match mode {
+                PayoutMode::Push => {
+                    sac.transfer(&env.current_contract_address(), &r, &share);
+
+                    // One event per payout for off-chain reconciliation
+                    env.events()
+                        .publish((symbol_short!("payout"), token.clone(), r), share);
+                }
+                PayoutMode::Claim => {
+                    let key = DataKey::Claimable(token.clone(), r.clone());
+                    let credited: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+                    env.storage().persistent().set(&key, &(credited + share));
+                    env.storage()
+                        .persistent()
+                        .extend_ttl(&key, ONE_YEAR_LEDGERS, ONE_YEAR_LEDGERS);
+
+                    env.events()
+                        .publish((symbol_short!("credit"), token.clone(), r), share);
+                }
+            }
+        }
+    }
+
+    if mode == PayoutMode::Claim {
+        env.storage().instance().set(
+            &DataKey::Owed(token.clone()),
+            &(owed(env, token) + distributed),
+        );
+    }
+
+    distributed
+}
+
+/// Record the outcome of a completed distribution of `amount`.
+fn finish_distribution(
+    env: &Env,
+    token: &Address,
+    amount: i128,
+    distributed: i128,
+    remainder_to: Option<u32>,
+) {
+    // Move the round-robin cursor past whoever received this leftover
+    if let Some(index) = remainder_to {
+        if remainder_policy(env) == RemainderPolicy::RoundRobin {
             env.storage()
                 .instance()
-                .get(&symbol_short!("shares"))
-                .unwrap(),
-        )
+                .set(&symbol_short!("rr"), &(index + 1));
+        }
     }
+
+    env.storage()
+        .instance()
+        .set(&DataKey::Dust(token.clone()), &(amount - distributed));
+
+    // Only a distribution that paid something starts the rate-limit gap
+    if distributed > 0 {
+        env.storage().instance().set(
+            &DataKey::LastDistribution(token.clone()),
+            &env.ledger().sequence(),
+        );
+    }
+
+    // Summary event: total paid out, remainder kept, and ledger sequence
+    env.events().publish(
+        (symbol_short!("distrib"), token.clone()),
+        (distributed, amount - distributed, env.ledger().sequence()),
+    );
+}
+
+/// Current recipients and their shares.
+fn load_split(env: &Env) -> Result<(Vec<Address>, Vec<u32>), Error> {
+    if storage_layout(env) == StorageLayout::Instance {
+        let recipients: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("recips"))
+            .ok_or(Error::NotInitialized)?;
+        let shares: Vec<u32> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("shares"))
+            .ok_or(Error::NotInitialized)?;
+        return Ok((recipients, shares));
+    }
+
+    load_range(env, 0, recipient_count(env)?)
+}
+
+/// Recipients and shares with indices in `start..end`.
+fn load_range(env: &Env, start: u32, end: u32) -> Result<(Vec<Address>, Vec<u32>), Error> {
+    if storage_layout(env) == StorageLayout::Instance {
+        let (recipients, shares) = load_split(env)?;
+        return Ok((recipients.slice(start..end), shares.slice(start..end)));
+    }
+
+    let mut recipients = Vec::new(env);
+    let mut shares = Vec::new(env);
+    let mut index = start;
+    while index < end {
+        let page = index / RECIPIENTS_PER_PAGE;
+        let key = DataKey::RecipientPage(page);
+        let (page_recipients, page_shares): (Vec<Address>, Vec<u32>) = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(Error::NotInitialized)?;
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, ONE_YEAR_LEDGERS, ONE_YEAR_LEDGERS);
+
+        let first = page * RECIPIENTS_PER_PAGE;
+        let last = end.min(first + RECIPIENTS_PER_PAGE);
+        recipients.append(&page_recipients.slice(index - first..last - first));
+        shares.append(&page_shares.slice(index - first..last - first));
+        index = last;
+    }
+    Ok((recipients, shares))
+}
+
+/// Number of recipients in the current split.
+fn recipient_count(env: &Env) -> Result<u32, Error> {
+    if storage_layout(env) == StorageLayout::Instance {
+        return Ok(load_split(env)?.0.len());
+    }
+    env.storage()
+        .instance()
+        .get(&symbol_short!("count"))
+        .ok_or(Error::NotInitialized)
+}
+
+/// Total shares and the index of the largest shareholder (earliest on ties).
+/// Stored for the persistent layout so batches need not load every entry.
+fn split_summary(env: &Env) -> Result<(u128, u32), Error> {
+    match env.storage().instance().get(&symbol_short!("summary")) {
+        Some(summary) => Ok(summary),
+        None => Ok(summarize_shares(&load_split(env)?.1)),
+    }
+}
+
+fn summarize_shares(shares: &Vec<u32>) -> (u128, u32) {
+    let total = shares.iter().map(|s| s as u128).sum();
+    let largest = shares
+        .iter()
+        .max()
+        .and_then(|max| shares.first_index_of(max))
+        .unwrap_or(0);
+    (total, largest)
+}
+
+/// Write a validated split using the layout chosen at init.
+fn store_split(env: &Env, recipients: &Vec<Address>, shares: &Vec<u32>) {
+    if storage_layout(env) == StorageLayout::Instance {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("recips"), recipients);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("shares"), shares);
+        return;
+    }
+
+    let previous: u32 = env
+        .storage()
+        .instance()
+        .get(&symbol_short!("count"))
+        .unwrap_or(0);
+
+    let pages = recipients.len().div_ceil(RECIPIENTS_PER_PAGE);
+    for page in 0..pages {
+        let first = page * RECIPIENTS_PER_PAGE;
+        let last = recipients.len().min(first + RECIPIENTS_PER_PAGE);
+        let key = DataKey::RecipientPage(page);
+        env.storage().persistent().set(
+            &key,
+            &(recipients.slice(first..last), shares.slice(first..last)),
+        );
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, ONE_YEAR_LEDGERS, ONE_YEAR_LEDGERS);
+    }
+    // Drop pages left over from a longer previous split
+    for page in pages..previous.div_ceil(RECIPIENTS_PER_PAGE) {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::RecipientPage(page));
+    }
+
+    env.storage()
+        .instance()
+        .set(&symbol_short!("count"), &recipients.len());
+    // Batched distributions need these without reading every entry
+    env.storage()
+        .instance()
+        .set(&symbol_short!("summary"), &summarize_shares(shares));
+}
+
+/// Where the split is stored; `Instance` for splitters created before layouts.
+fn storage_layout(env: &Env) -> StorageLayout {
+    env.storage()
+        .instance()
+        .get(&symbol_short!("layout"))
+        .unwrap_or_default()
+}
+
+/// Balance of `token` that can be split: the contract's holdings minus funds
+/// already credited to recipients in claim mode.
+fn available_balance(env: &Env, token: &Address) -> Result<i128, Error> {
+    let sac = soroban_sdk::token::Client::new(env, token);
+    let balance = sac.balance(&env.current_contract_address()) - owed(env, token);
+
+    // Ensure balance is non-negative (should always be true for tokens, but be safe)
+    if balance < 0 {
+        return Err(Error::NegativeBalance);
+    }
+
+    Ok(balance)
+}
+
+fn remainder_policy(env: &Env) -> RemainderPolicy {
+    env.storage()
+        .instance()
+        .get(&symbol_short!("policy"))
+        .unwrap_or_default()
+}
+
+/// Splits `amount` in proportion to `shares` using checked arithmetic, then
+/// adds the integer-division leftover to the recipient picked by the remainder
+/// policy. Returns the payouts in recipient order and the index that received
+/// the leftover, if any.
+fn split_amount(
+    env: &Env,
+    shares: &Vec<u32>,
+    amount: i128,
+) -> Result<(Vec<i128>, Option<u32>), Error> {
+    let total: u128 = shares.iter().map(|s| s as u128).sum();
+    let mut payouts = proportional_payouts(env, shares, total, amount)?;
+    let allocated: i128 = payouts.iter().sum();
+
+    let remainder = amount - allocated;
+    if remainder == 0 {
+        return Ok((payouts, None));
+    }
+
+    let index = remainder_index(env, shares.len(), summarize_shares(shares).1, |i| {
+        Ok(shares.get_unchecked(i))
+    })?;
+    if let Some(i) = index {
+        payouts.set(i, payouts.get_unchecked(i) + remainder);
+    }
+
+    Ok((payouts, index))
+}
+
+/// `amount * share / total` for each share, rounded down.
+fn proportional_payouts(
+    env: &Env,
+    shares: &Vec<u32>,
+    total: u128,
+    amount: i128,
+) -> Result<Vec<i128>, Error> {
+    let amount_u128 = amount as u128;
+
+    let mut payouts = Vec::new(env);
+    for share_ratio in shares.iter() {
+        // Use checked arithmetic to prevent overflow
+        let share = amount_u128
+            .checked_mul(share_ratio as u128)
+            .ok_or(Error::Overflow)?
+            .checked_div(total)
+            .ok_or(Error::Overflow)?; // total > 0 guaranteed by init validation
+
+        // Ensure share fits in i128
+        let share = i128::try_from(share).map_err(|_| Error::Overflow)?;
+        payouts.push_back(share);
+    }
+    Ok(payouts)
+}
+
+/// Recipient index that receives the integer-division leftover under the
+/// remainder policy, or `None` to keep it. `share_at` looks up a share so
+/// batched rounds need not load the whole split.
+fn remainder_index(
+    env: &Env,
+    count: u32,
+    largest: u32,
+    share_at: impl Fn(u32) -> Result<u32, Error>,
+) -> Result<Option<u32>, Error> {
+    // Every policy other than Keep picks a recipient with a non-zero share;
+    // one always exists because init rejects an all-zero share table.
+    let start = match remainder_policy(env) {
+        RemainderPolicy::Keep => return Ok(None),
+        RemainderPolicy::FirstRecipient => 0,
+        RemainderPolicy::LargestShareholder => return Ok(Some(largest)),
+        RemainderPolicy::RoundRobin => env
+            .storage()
+            .instance()
+            .get(&symbol_short!("rr"))
+            .unwrap_or(0),
+    };
+
+    for offset in 0..count {
+        let index = (start + offset) % count;
+        if share_at(index)? > 0 {
+            return Ok(Some(index));
+        }
+    }
+    Ok(None)
+}
+
+/// Total of `token` credited to recipients but not yet claimed.
+fn owed(env: &Env, token: &Address) -> i128 {
+    env.storage()
+        .instance()
+        .get(&DataKey::Owed(token.clone()))
+        .unwrap_or(0)
+}
+
+/// Most recipients `update_recipients` accepts; `MAX_RECIPIENTS` for splitters
+/// initialized before the limit was stored.
+fn max_recipients(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&symbol_short!("maxrecip"))
+        .unwrap_or(MAX_RECIPIENTS)
+}
+
+/// How stored shares are read; `Ratio` for splitters created before share modes.
+fn share_mode(env: &Env) -> ShareMode {
+    env.storage()
+        .instance()
+        .get(&symbol_short!("sharemode"))
+        .unwrap_or_default()
+}
+
+/// Each share as a portion of the total in basis points, rounded down.
+fn percentages_bps(env: &Env, shares: &Vec<u32>) -> Vec<u32> {
+    let total: u64 = shares.iter().map(|s| s as u64).sum();
+    let mut percentages = Vec::new(env);
+    for share in shares.iter() {
+        // total is non-zero for any stored split
+        percentages.push_back((share as u64 * TOTAL_BASIS_POINTS as u64 / total) as u32);
+    }
+    percentages
+}
+
+/// Checks a recipients/shares pair before it is stored.
+/// Zero-share entries are allowed; they simply receive nothing.
+fn validate_split(
+    env: &Env,
+    recipients: &Vec<Address>,
+    shares: &Vec<u32>,
+    max_recipients: u32,
+    share_mode: ShareMode,
+) -> Result<(), Error> {
+    if recipients.len() != shares.len() {
+        return Err(Error::LengthMismatch);
+    }
+
+    if recipients.len() > max_recipients {
+        return Err(Error::TooManyRecipients);
+    }
+
+    let this = env.current_contract_address();
+    for (i, recipient) in recipients.iter().enumerate() {
+        if recipient == this {
+            return Err(Error::SelfRecipient);
+        }
+        if recipients.first_index_of(&recipient) != Some(i as u32) {
+            return Err(Error::DuplicateRecipient);
+        }
+    }
+
+    // Validate that at least one share is non-zero to prevent division by zero
+    let total: u128 = shares.iter().map(|s| s as u128).sum();
+    if total == 0 {
+        return Err(Error::ZeroTotalShares);
+    }
+
+    if share_mode == ShareMode::BasisPoints && total != TOTAL_BASIS_POINTS as u128 {
+        return Err(Error::InvalidBasisPoints);
+    }
+
+    Ok(())
 }
@@ -1,7 +1,12 @@
 #![cfg(test)]
+extern crate std;
 
 use super::*;
-use soroban_sdk::{contract, contractimpl, testutils::Address as _, token, vec, Address, Env};
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{storage::Persistent as _, Address as _, Events, IssuerFlags, Ledger},
+    token, vec, Address, Env, IntoVal, Val,
+};
 
 // Malicious contract that attempts reentrancy
 #[contract]
@@ -28,12 +33,58 @@ fn create_contract(env: &Env) -> (Address, SimpleSplitterClient<'_>) {
     (contract_id, client)
 }
 
+fn init_claim_mode(
+    client: &SimpleSplitterClient,
+    token: &Address,
+    recipients: Vec<Address>,
+    shares: Vec<u32>,
+) {
+    client.init_with_options(
+        token,
+        &recipients,
+        &shares,
+        &SplitterOptions {
+            payout_mode: PayoutMode::Claim,
+            ..Default::default()
+        },
+    );
+}
+
+fn init_with_policy(
+    client: &SimpleSplitterClient,
+    token: &Address,
+    recipients: Vec<Address>,
+    shares: Vec<u32>,
+    remainder_policy: RemainderPolicy,
+) {
+    client.init_with_options(
+        token,
+        &recipients,
+        &shares,
+        &SplitterOptions {
+            remainder_policy,
+            ..Default::default()
+        },
+    );
+}
+
 fn create_token(env: &Env) -> Address {
     let token_admin = Address::generate(env);
     let token = env.register_stellar_asset_contract_v2(token_admin);
     token.address()
 }
 
+// Events published by the splitter itself, excluding token contract events
+fn splitter_events(env: &Env, contract_id: &Address) -> Vec<(Address, Vec<Val>, Val)> {
+    let mut events = Vec::new(env);
+    for event in env.events().all().iter() {
+        if event.0 == *contract_id {
+            events.push_back(event);
+        }
+    }
+    events
+}
+
 fn mint_tokens(env: &Env, contract_id: &Address, token_address: &Address, amount: i128) {
     let token_admin_client = token::StellarAssetClient::new(env, token_address);
     token_admin_client.mint(contract_id, &amount);
@@ -42,6 +93,7 @@ fn mint_tokens(env: &Env, contract_id: &Address, token_address: &Address, amount
 #[test]
 fn test_split() {
     let env = setup_test_env();
+    let keeper = Address::generate(&env);
     let (contract_id, client) = create_contract(&env);
     let token = create_token(&env);
 
@@ -55,7 +107,7 @@ fn test_split() {
     );
 
     mint_tokens(&env, &contract_id, &token, 100);
-    client.distribute();
+    client.distribute(&keeper);
 
     let sac = token::Client::new(&env, &token);
     assert_eq!(sac.balance(&alice), 50);
@@ -65,6 +117,7 @@ fn test_split() {
 #[test]
 fn test_unequal_shares() {
     let env = setup_test_env();
+    let keeper = Address::generate(&env);
     let (contract_id, client) = create_contract(&env);
     let token = create_token(&env);
 
@@ -79,7 +132,7 @@ fn test_unequal_shares() {
     );
 
     mint_tokens(&env, &contract_id, &token, 99); // Use 99 to test even division
-    client.distribute();
+    client.distribute(&keeper);
 
     let sac = token::Client::new(&env, &token);
     // Alice should get 66 (2/3 of 99), Bob should get 33 (1/3 of 99)
@@ -90,6 +143,7 @@ fn test_unequal_shares() {
 #[test]
 fn test_multiple_recipients() {
     let env = setup_test_env();
+    let keeper = Address::generate(&env);
     let (contract_id, client) = create_contract(&env);
     let token = create_token(&env);
 
@@ -105,7 +159,7 @@ fn test_multiple_recipients() {
     );
 
     mint_tokens(&env, &contract_id, &token, 120); // Divide evenly by 3
-    client.distribute();
+    client.distribute(&keeper);
 
     let sac = token::Client::new(&env, &token);
     // Each should get 40 tokens (120 / 3)
@@ -115,7 +169,6 @@ fn test_multiple_recipients() {
 }
 
 #[test]
-#[should_panic(expected = "length mismatch")]
 fn test_mismatched_lengths() {
     let env = setup_test_env();
     let (_contract_id, client) = create_contract(&env);
@@ -124,17 +177,19 @@ fn test_mismatched_lengths() {
     let alice = Address::generate(&env);
     let bob = Address::generate(&env);
 
-    // Recipients: 2, Shares: 3 (mismatch should panic)
-    client.init(
+    // Recipients: 2, Shares: 3 (mismatch should fail)
+    let result = client.try_init(
         &token,
         &vec![&env, alice.clone(), bob.clone()],
         &vec![&env, 1, 2, 3],
     );
+    assert_eq!(result, Err(Ok(Error::LengthMismatch)));
 }
 
 #[test]
 fn test_zero_balance_distribution() {
     let env = setup_test_env();
+    let keeper = Address::generate(&env);
     let (_contract_id, client) = create_contract(&env);
     let token = create_token(&env);
 
@@ -148,7 +203,7 @@ fn test_zero_balance_distribution() {
     );
 
     // Don't mint any tokens - balance is 0
-    client.distribute();
+    client.distribute(&keeper);
 
     let sac = token::Client::new(&env, &token);
     // Both should still have 0 balance
@@ -159,6 +214,7 @@ fn test_zero_balance_distribution() {
 #[test]
 fn test_rounding_remainder() {
     let env = setup_test_env();
+    let keeper = Address::generate(&env);
     let (contract_id, client) = create_contract(&env);
     let token = create_token(&env);
 
@@ -174,7 +230,7 @@ fn test_rounding_remainder() {
     );
 
     mint_tokens(&env, &contract_id, &token, 100); // 100 / 3 = 33.33...
-    client.distribute();
+    client.distribute(&keeper);
 
     let sac = token::Client::new(&env, &token);
 
@@ -186,22 +242,24 @@ fn test_rounding_remainder() {
 
     // Contract should still have 1 token left (the remainder)
     assert_eq!(sac.balance(&contract_id), 1);
+    assert_eq!(client.dust(&token), 1);
 }
 
 #[test]
-#[should_panic(expected = "total shares must be greater than zero")]
 fn test_empty_recipients() {
     let env = setup_test_env();
     let (_contract_id, client) = create_contract(&env);
     let token = create_token(&env);
 
-    // Initialize with empty vectors - should panic because total shares = 0
-    client.init(&token, &vec![&env], &vec![&env]);
+    // Initialize with empty vectors - should fail because total shares = 0
+    let result = client.try_init(&token, &vec![&env], &vec![&env]);
+    assert_eq!(result, Err(Ok(Error::ZeroTotalShares)));
 }
 
 #[test]
 fn test_zero_shares() {
     let env = setup_test_env();
+    let keeper = Address::generate(&env);
     let (contract_id, client) = create_contract(&env);
     let token = create_token(&env);
 
@@ -217,7 +275,7 @@ fn test_zero_shares() {
     );
 
     mint_tokens(&env, &contract_id, &token, 90);
-    client.distribute();
+    client.distribute(&keeper);
 
     let sac = token::Client::new(&env, &token);
     // Alice should get 60 (2/3 of 90), Bob should get 0, Charlie should get 30 (1/3 of 90)
@@ -227,15 +285,24 @@ fn test_zero_shares() {
 }
 
 #[test]
-#[should_panic]
 fn test_distribute_before_init() {
+    let env = setup_test_env();
+    let keeper = Address::generate(&env);
+    let (_contract_id, client) = create_contract(&env);
+    assert_eq!(
+        client.try_distribute(&keeper),
+        Err(Ok(Error::NotInitialized))
+    );
+}
+
+#[test]
+fn test_get_config_before_init() {
     let env = setup_test_env();
     let (_contract_id, client) = create_contract(&env);
-    client.distribute();
+    assert_eq!(client.try_get_config(), Err(Ok(Error::NotInitialized)));
 }
 
 #[test]
-#[should_panic(expected = "already initialized")]
 fn test_cannot_reinitialize() {
     let env = setup_test_env();
     let (_contract_id, client) = create_contract(&env);
@@ -251,17 +318,17 @@ fn test_cannot_reinitialize() {
         &vec![&env, 1, 1],
     );
 
-    // Second initialization should panic
+    // Second initialization should fail
     let charlie = Address::generate(&env);
-    client.init(
+    let result = client.try_init(
         &token,
         &vec![&env, alice.clone(), charlie.clone()],
         &vec![&env, 1, 1],
     );
+    assert_eq!(result, Err(Ok(Error::AlreadyInitialized)));
 }
 
 #[test]
-#[should_panic(expected = "total shares must be greater than zero")]
 fn test_all_zero_shares() {
     let env = setup_test_env();
     let (_contract_id, client) = create_contract(&env);
@@ -270,18 +337,19 @@ fn test_all_zero_shares() {
     let alice = Address::generate(&env);
     let bob = Address::generate(&env);
 
-    // Initialize with all zero shares should panic
-    client.init(
+    // Initialize with all zero shares should fail
+    let result = client.try_init(
         &token,
         &vec![&env, alice.clone(), bob.clone()],
         &vec![&env, 0, 0],
     );
+    assert_eq!(result, Err(Ok(Error::ZeroTotalShares)));
 }
 
 #[test]
-#[should_panic(expected = "overflow in share calculation")]
 fn test_overflow_detection() {
     let env = setup_test_env();
+    let keeper = Address::generate(&env);
     let (contract_id, client) = create_contract(&env);
     let token = create_token(&env);
 
@@ -298,13 +366,14 @@ fn test_overflow_detection() {
     // Mint a large balance that will cause overflow
     mint_tokens(&env, &contract_id, &token, i128::MAX);
 
-    // This should panic with overflow detection
-    client.distribute();
+    // This should fail with overflow detection
+    assert_eq!(client.try_distribute(&keeper), Err(Ok(Error::Overflow)));
 }
 
 #[test]
 fn test_large_values_no_overflow() {
     let env = setup_test_env();
+    let keeper = Address::generate(&env);
     let (contract_id, client) = create_contract(&env);
     let token = create_token(&env);
 
@@ -322,7 +391,7 @@ fn test_large_values_no_overflow() {
     mint_tokens(&env, &contract_id, &token, 1_000_000_000_000);
 
     // This should work without overflow
-    client.distribute();
+    client.distribute(&keeper);
 
     let sac = token::Client::new(&env, &token);
     // Both should get half
@@ -333,6 +402,7 @@ fn test_large_values_no_overflow() {
 #[test]
 fn test_reentrancy_guard() {
     let env = setup_test_env();
+    let keeper = Address::generate(&env);
     let (contract_id, client) = create_contract(&env);
     let token = create_token(&env);
 
@@ -348,7 +418,7 @@ fn test_reentrancy_guard() {
     mint_tokens(&env, &contract_id, &token, 100);
 
     // First call should succeed
-    client.distribute();
+    client.distribute(&keeper);
 
     let sac = token::Client::new(&env, &token);
     assert_eq!(sac.balance(&alice), 50);
@@ -356,9 +426,2732 @@ fn test_reentrancy_guard() {
 
     // Verify the lock was released (we can call distribute again)
     mint_tokens(&env, &contract_id, &token, 100);
-    client.distribute();
+    client.distribute(&keeper);
 
     // Should work again, distributing the new tokens
     assert_eq!(sac.balance(&alice), 100);
     assert_eq!(sac.balance(&bob), 100);
 }
+
+#[test]
+fn test_distribute_rejected_while_locked() {
+    let env = setup_test_env();
+    let keeper = Address::generate(&env);
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+
+    let alice = Address::generate(&env);
+
+    client.init(&token, &vec![&env, alice.clone()], &vec![&env, 1]);
+    mint_tokens(&env, &contract_id, &token, 100);
+
+    // Simulate a distribution already in progress
+    env.as_contract(&contract_id, || {
+        env.storage().instance().set(&symbol_short!("lock"), &true);
+    });
+
+    assert_eq!(client.try_distribute(&keeper), Err(Ok(Error::Reentrancy)));
+
+    let sac = token::Client::new(&env, &token);
+    assert_eq!(sac.balance(&alice), 0);
+}
+
+#[test]
+fn test_distribute_emits_payout_events() {
+    let env = setup_test_env();
+    let keeper = Address::generate(&env);
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+
+    client.init(
+        &token,
+        &vec![&env, alice.clone(), bob.clone(), charlie.clone()],
+        &vec![&env, 1, 1, 1],
+    );
+
+    mint_tokens(&env, &contract_id, &token, 100);
+    client.distribute(&keeper);
+
+    let ledger = env.ledger().sequence();
+    assert_eq!(
+        splitter_events(&env, &contract_id),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("payout"), token.clone(), alice.clone()).into_val(&env),
+                33i128.into_val(&env),
+            ),
+            (
+                contract_id.clone(),
+                (symbol_short!("payout"), token.clone(), bob.clone()).into_val(&env),
+                33i128.into_val(&env),
+            ),
+            (
+                contract_id.clone(),
+                (symbol_short!("payout"), token.clone(), charlie.clone()).into_val(&env),
+                33i128.into_val(&env),
+            ),
+            (
+                contract_id.clone(),
+                (symbol_short!("distrib"), token.clone()).into_val(&env),
+                (99i128, 1i128, ledger).into_val(&env),
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_zero_share_recipient_gets_no_payout_event() {
+    let env = setup_test_env();
+    let keeper = Address::generate(&env);
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    client.init(
+        &token,
+        &vec![&env, alice.clone(), bob.clone()],
+        &vec![&env, 1, 0],
+    );
+
+    mint_tokens(&env, &contract_id, &token, 10);
+    client.distribute(&keeper);
+
+    let ledger = env.ledger().sequence();
+    assert_eq!(
+        splitter_events(&env, &contract_id),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("payout"), token.clone(), alice.clone()).into_val(&env),
+                10i128.into_val(&env),
+            ),
+            (
+                contract_id.clone(),
+                (symbol_short!("distrib"), token.clone()).into_val(&env),
+                (10i128, 0i128, ledger).into_val(&env),
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_zero_balance_emits_summary_only() {
+    let env = setup_test_env();
+    let keeper = Address::generate(&env);
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+
+    let alice = Address::generate(&env);
+
+    client.init(&token, &vec![&env, alice.clone()], &vec![&env, 1]);
+    client.distribute(&keeper);
+
+    let ledger = env.ledger().sequence();
+    assert_eq!(
+        splitter_events(&env, &contract_id),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("distrib"), token.clone()).into_val(&env),
+                (0i128, 0i128, ledger).into_val(&env),
+            ),
+        ]
+    );
+}
+
+fn init_with_owner(
+    client: &SimpleSplitterClient,
+    token: &Address,
+    owner: &Address,
+    recipients: Vec<Address>,
+    shares: Vec<u32>,
+) {
+    client.init_with_options(
+        token,
+        &recipients,
+        &shares,
+        &SplitterOptions {
+            owner: Some(owner.clone()),
+            ..Default::default()
+        },
+    );
+    assert_eq!(client.owner(), Some(owner.clone()));
+}
+
+#[test]
+fn test_owner_updates_recipients() {
+    let env = setup_test_env();
+    let keeper = Address::generate(&env);
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+
+    let owner = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+
+    init_with_owner(
+        &client,
+        &token,
+        &owner,
+        vec![&env, alice.clone(), bob.clone()],
+        vec![&env, 1, 1],
+    );
+
+    // Bob leaves, Charlie joins with double weight
+    let new_recipients = vec![&env, alice.clone(), charlie.clone()];
+    let new_shares = vec![&env, 1u32, 2u32];
+    client.update_recipients(&new_recipients, &new_shares);
+
+    // The owner must have authorized the update
+    assert_eq!(env.auths().len(), 1);
+    assert_eq!(env.auths()[0].0, owner);
+
+    assert_eq!(
+        splitter_events(&env, &contract_id),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("updated"),).into_val(&env),
+                (new_recipients.clone(), new_shares.clone()).into_val(&env),
+            ),
+        ]
+    );
+
+    let config = client.get_config();
+    assert_eq!(config.recipients, new_recipients);
+    assert_eq!(config.shares, new_shares);
+
+    mint_tokens(&env, &contract_id, &token, 90);
+    client.distribute(&keeper);
+
+    let sac = token::Client::new(&env, &token);
+    assert_eq!(sac.balance(&alice), 30);
+    assert_eq!(sac.balance(&bob), 0);
+    assert_eq!(sac.balance(&charlie), 60);
+}
+
+#[test]
+fn test_update_recipients_validated_like_init() {
+    let env = setup_test_env();
+    let (_contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+
+    let owner = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    init_with_owner(
+        &client,
+        &token,
+        &owner,
+        vec![&env, alice.clone(), bob.clone()],
+        vec![&env, 1, 1],
+    );
+
+    assert_eq!(
+        client.try_update_recipients(&vec![&env, alice.clone()], &vec![&env, 1, 1]),
+        Err(Ok(Error::LengthMismatch))
+    );
+    assert_eq!(
+        client.try_update_recipients(&vec![&env, alice.clone(), bob.clone()], &vec![&env, 0, 0]),
+        Err(Ok(Error::ZeroTotalShares))
+    );
+    assert_eq!(
+        client.try_update_recipients(&vec![&env], &vec![&env]),
+        Err(Ok(Error::ZeroTotalShares))
+    );
+
+    // Config is unchanged
+    let config = client.get_config();
+    assert_eq!(config.recipients, vec![&env, alice.clone(), bob.clone()]);
+    assert_eq!(config.shares, vec![&env, 1, 1]);
+}
+
+#[test]
+fn test_update_recipients_without_owner_is_immutable() {
+    let env = setup_test_env();
+    let (_contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    client.init(&token, &vec![&env, alice.clone()], &vec![&env, 1]);
+    assert_eq!(client.owner(), None);
+
+    assert_eq!(
+        client.try_update_recipients(&vec![&env, bob.clone()], &vec![&env, 1]),
+        Err(Ok(Error::Immutable))
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_update_recipients_requires_owner_auth() {
+    let env = Env::default();
+    let (_contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+
+    let owner = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    client.init_with_options(
+        &token,
+        &vec![&env, alice.clone()],
+        &vec![&env, 1],
+        &SplitterOptions {
+            owner: Some(owner),
+            ..Default::default()
+        },
+    );
+
+    // No auths are mocked, so the owner check fails
+    client.update_recipients(&vec![&env, alice.clone()], &vec![&env, 2]);
+}
+
+#[test]
+fn test_two_step_ownership_transfer() {
+    let env = setup_test_env();
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+
+    let owner = Address::generate(&env);
+    let multisig = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    init_with_owner(
+        &client,
+        &token,
+        &owner,
+        vec![&env, alice.clone()],
+        vec![&env, 1],
+    );
+
+    client.propose_owner(&multisig);
+    assert_eq!(env.auths()[0].0, owner);
+    assert_eq!(
+        splitter_events(&env, &contract_id),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("proposed"),).into_val(&env),
+                (owner.clone(), multisig.clone()).into_val(&env),
+            ),
+        ]
+    );
+
+    // Proposal alone does not change the owner
+    assert_eq!(client.owner(), Some(owner.clone()));
+    assert_eq!(client.pending_owner(), Some(multisig.clone()));
+
+    client.accept_owner();
+    assert_eq!(env.auths()[0].0, multisig);
+    assert_eq!(
+        splitter_events(&env, &contract_id),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("accepted"),).into_val(&env),
+                (owner.clone(), multisig.clone()).into_val(&env),
+            ),
+        ]
+    );
+
+    assert_eq!(client.owner(), Some(multisig.clone()));
+    assert_eq!(client.pending_owner(), None);
+
+    let config = client.get_config();
+    assert_eq!(config.owner, Some(multisig.clone()));
+    assert!(!config.immutable);
+
+    // Admin calls now require the new owner
+    client.update_recipients(&vec![&env, alice.clone()], &vec![&env, 2]);
+    assert_eq!(env.auths()[0].0, multisig);
+}
+
+#[test]
+fn test_accept_owner_without_proposal() {
+    let env = setup_test_env();
+    let (_contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+
+    let owner = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    init_with_owner(
+        &client,
+        &token,
+        &owner,
+        vec![&env, alice.clone()],
+        vec![&env, 1],
+    );
+
+    assert_eq!(client.try_accept_owner(), Err(Ok(Error::NoPendingOwner)));
+}
+
+#[test]
+fn test_renounce_ownership_freezes_splitter() {
+    let env = setup_test_env();
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+
+    let owner = Address::generate(&env);
+    let multisig = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    init_with_owner(
+        &client,
+        &token,
+        &owner,
+        vec![&env, alice.clone()],
+        vec![&env, 1],
+    );
+    assert!(!client.is_immutable());
+
+    // A pending proposal is discarded on renounce
+    client.propose_owner(&multisig);
+    client.renounce_ownership();
+    assert_eq!(env.auths()[0].0, owner);
+    assert_eq!(
+        splitter_events(&env, &contract_id),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("renounced"),).into_val(&env),
+                owner.clone().into_val(&env),
+            ),
+        ]
+    );
+
+    assert!(client.is_immutable());
+    assert_eq!(client.owner(), None);
+    assert_eq!(client.pending_owner(), None);
+
+    let config = client.get_config();
+    assert_eq!(config.owner, None);
+    assert!(config.immutable);
+
+    // Every admin call is rejected
+    assert_eq!(
+        client.try_update_recipients(&vec![&env, multisig.clone()], &vec![&env, 1]),
+        Err(Ok(Error::Immutable))
+    );
+    assert_eq!(
+        client.try_propose_owner(&multisig),
+        Err(Ok(Error::Immutable))
+    );
+    assert_eq!(client.try_accept_owner(), Err(Ok(Error::Immutable)));
+    assert_eq!(client.try_renounce_ownership(), Err(Ok(Error::Immutable)));
+}
+
+#[test]
+fn test_splitter_without_owner_reports_immutable() {
+    let env = setup_test_env();
+    let (_contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+
+    let alice = Address::generate(&env);
+
+    client.init(&token, &vec![&env, alice.clone()], &vec![&env, 1]);
+
+    assert!(client.is_immutable());
+    let config = client.get_config();
+    assert_eq!(config.token, token);
+    assert_eq!(config.owner, None);
+    assert!(config.immutable);
+    assert_eq!(config.payout_mode, PayoutMode::Push);
+
+    assert_eq!(client.try_propose_owner(&alice), Err(Ok(Error::Immutable)));
+}
+
+#[test]
+fn test_claim_mode_credits_instead_of_transfers() {
+    let env = setup_test_env();
+    let keeper = Address::generate(&env);
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    init_claim_mode(
+        &client,
+        &token,
+        vec![&env, alice.clone(), bob.clone()],
+        vec![&env, 1, 1],
+    );
+
+    mint_tokens(&env, &contract_id, &token, 100);
+    client.distribute(&keeper);
+
+    let ledger = env.ledger().sequence();
+    assert_eq!(
+        splitter_events(&env, &contract_id),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("credit"), token.clone(), alice.clone()).into_val(&env),
+                50i128.into_val(&env),
+            ),
+            (
+                contract_id.clone(),
+                (symbol_short!("credit"), token.clone(), bob.clone()).into_val(&env),
+                50i128.into_val(&env),
+            ),
+            (
+                contract_id.clone(),
+                (symbol_short!("distrib"), token.clone()).into_val(&env),
+                (100i128, 0i128, ledger).into_val(&env),
+            ),
+        ]
+    );
+
+    assert_eq!(client.get_config().payout_mode, PayoutMode::Claim);
+
+    // Nothing moved yet; funds are held for the recipients
+    let sac = token::Client::new(&env, &token);
+    assert_eq!(sac.balance(&alice), 0);
+    assert_eq!(sac.balance(&bob), 0);
+    assert_eq!(sac.balance(&contract_id), 100);
+    assert_eq!(client.claimable(&alice), 50);
+    assert_eq!(client.claimable(&bob), 50);
+
+    assert_eq!(client.claim(&alice), 50);
+    assert_eq!(env.auths()[0].0, alice);
+    assert_eq!(
+        splitter_events(&env, &contract_id),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("claim"), token.clone(), alice.clone()).into_val(&env),
+                50i128.into_val(&env),
+            ),
+        ]
+    );
+    assert_eq!(sac.balance(&alice), 50);
+    assert_eq!(client.claimable(&alice), 0);
+
+    // Claiming again is a no-op
+    assert_eq!(client.claim(&alice), 0);
+    assert_eq!(sac.balance(&alice), 50);
+}
+
+#[test]
+fn test_claim_mode_excludes_unclaimed_funds_from_next_distribution() {
+    let env = setup_test_env();
+    let keeper = Address::generate(&env);
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    init_claim_mode(
+        &client,
+        &token,
+        vec![&env, alice.clone(), bob.clone()],
+        vec![&env, 1, 1],
+    );
+
+    mint_tokens(&env, &contract_id, &token, 100);
+    client.distribute(&keeper);
+    client.claim(&alice);
+
+    // Bob's unclaimed 50 must not be split again
+    mint_tokens(&env, &contract_id, &token, 10);
+    client.distribute(&keeper);
+
+    assert_eq!(client.claimable(&alice), 5);
+    assert_eq!(client.claimable(&bob), 55);
+
+    // Distributing again with no new deposits changes nothing
+    client.distribute(&keeper);
+    assert_eq!(client.claimable(&alice), 5);
+    assert_eq!(client.claimable(&bob), 55);
+
+    client.claim(&alice);
+    client.claim(&bob);
+
+    let sac = token::Client::new(&env, &token);
+    assert_eq!(sac.balance(&alice), 55);
+    assert_eq!(sac.balance(&bob), 55);
+    assert_eq!(sac.balance(&contract_id), 0);
+}
+
+#[test]
+fn test_claim_mode_survives_frozen_recipient() {
+    let env = setup_test_env();
+    let keeper = Address::generate(&env);
+    let (contract_id, client) = create_contract(&env);
+    let token_admin = Address::generate(&env);
+    let asset = env.register_stellar_asset_contract_v2(token_admin);
+    asset.issuer().set_flag(IssuerFlags::RevocableFlag);
+    let token = asset.address();
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    init_claim_mode(
+        &client,
+        &token,
+        vec![&env, alice.clone(), bob.clone()],
+        vec![&env, 1, 1],
+    );
+
+    // The issuer freezes Bob
+    let admin_client = token::StellarAssetClient::new(&env, &token);
+    admin_client.set_authorized(&bob, &false);
+
+    mint_tokens(&env, &contract_id, &token, 100);
+    client.distribute(&keeper);
+
+    // Alice is paid even though Bob cannot receive funds
+    assert_eq!(client.claim(&alice), 50);
+    assert!(client.try_claim(&bob).is_err());
+    assert_eq!(client.claimable(&bob), 50);
+
+    // Once unfrozen, Bob can still collect
+    admin_client.set_authorized(&bob, &true);
+    assert_eq!(client.claim(&bob), 50);
+}
+
+#[test]
+fn test_push_mode_blocked_by_frozen_recipient() {
+    let env = setup_test_env();
+    let keeper = Address::generate(&env);
+    let (contract_id, client) = create_contract(&env);
+    let token_admin = Address::generate(&env);
+    let asset = env.register_stellar_asset_contract_v2(token_admin);
+    asset.issuer().set_flag(IssuerFlags::RevocableFlag);
+    let token = asset.address();
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    client.init(
+        &token,
+        &vec![&env, alice.clone(), bob.clone()],
+        &vec![&env, 1, 1],
+    );
+
+    token::StellarAssetClient::new(&env, &token).set_authorized(&bob, &false);
+    mint_tokens(&env, &contract_id, &token, 100);
+
+    assert!(client.try_distribute(&keeper).is_err());
+    assert_eq!(token::Client::new(&env, &token).balance(&alice), 0);
+}
+
+#[test]
+fn test_distribute_token_splits_other_tokens() {
+    let env = setup_test_env();
+    let keeper = Address::generate(&env);
+    let (contract_id, client) = create_contract(&env);
+    let pyusd = create_token(&env);
+    let usdc = create_token(&env);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    client.init(
+        &pyusd,
+        &vec![&env, alice.clone(), bob.clone()],
+        &vec![&env, 3, 1],
+    );
+
+    mint_tokens(&env, &contract_id, &pyusd, 40);
+    mint_tokens(&env, &contract_id, &usdc, 400);
+
+    client.distribute_token(&usdc);
+
+    let ledger = env.ledger().sequence();
+    assert_eq!(
+        splitter_events(&env, &contract_id),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("payout"), usdc.clone(), alice.clone()).into_val(&env),
+                300i128.into_val(&env),
+            ),
+            (
+                contract_id.clone(),
+                (symbol_short!("payout"), usdc.clone(), bob.clone()).into_val(&env),
+                100i128.into_val(&env),
+            ),
+            (
+                contract_id.clone(),
+                (symbol_short!("distrib"), usdc.clone()).into_val(&env),
+                (400i128, 0i128, ledger).into_val(&env),
+            ),
+        ]
+    );
+
+    let usdc_client = token::Client::new(&env, &usdc);
+    assert_eq!(usdc_client.balance(&alice), 300);
+    assert_eq!(usdc_client.balance(&bob), 100);
+
+    // The primary token is untouched until it is distributed
+    let pyusd_client = token::Client::new(&env, &pyusd);
+    assert_eq!(pyusd_client.balance(&contract_id), 40);
+
+    client.distribute(&keeper);
+    assert_eq!(pyusd_client.balance(&alice), 30);
+    assert_eq!(pyusd_client.balance(&bob), 10);
+}
+
+#[test]
+fn test_distribute_token_respects_allowlist() {
+    let env = setup_test_env();
+    let (contract_id, client) = create_contract(&env);
+    let pyusd = create_token(&env);
+    let usdc = create_token(&env);
+    let other = create_token(&env);
+
+    let alice = Address::generate(&env);
+
+    client.init_with_options(
+        &pyusd,
+        &vec![&env, alice.clone()],
+        &vec![&env, 1],
+        &SplitterOptions {
+            allowed_tokens: Some(vec![&env, usdc.clone()]),
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        client.get_config().allowed_tokens,
+        Some(vec![&env, usdc.clone()])
+    );
+
+    mint_tokens(&env, &contract_id, &pyusd, 10);
+    mint_tokens(&env, &contract_id, &usdc, 20);
+    mint_tokens(&env, &contract_id, &other, 30);
+
+    assert_eq!(
+        client.try_distribute_token(&other),
+        Err(Ok(Error::TokenNotAllowed))
+    );
+
+    // Allowed tokens and the primary token are both accepted
+    client.distribute_token(&usdc);
+    client.distribute_token(&pyusd);
+
+    assert_eq!(token::Client::new(&env, &usdc).balance(&alice), 20);
+    assert_eq!(token::Client::new(&env, &pyusd).balance(&alice), 10);
+    assert_eq!(token::Client::new(&env, &other).balance(&contract_id), 30);
+}
+
+#[test]
+fn test_claim_mode_tracks_each_token_separately() {
+    let env = setup_test_env();
+    let keeper = Address::generate(&env);
+    let (contract_id, client) = create_contract(&env);
+    let pyusd = create_token(&env);
+    let usdc = create_token(&env);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    init_claim_mode(
+        &client,
+        &pyusd,
+        vec![&env, alice.clone(), bob.clone()],
+        vec![&env, 1, 1],
+    );
+
+    mint_tokens(&env, &contract_id, &pyusd, 10);
+    mint_tokens(&env, &contract_id, &usdc, 100);
+    client.distribute(&keeper);
+    client.distribute_token(&usdc);
+
+    assert_eq!(client.claimable(&alice), 5);
+    assert_eq!(client.claimable_token(&alice, &usdc), 50);
+
+    assert_eq!(client.claim_token(&alice, &usdc), 50);
+    assert_eq!(client.claimable(&alice), 5);
+    assert_eq!(client.claimable_token(&alice, &usdc), 0);
+
+    // Unclaimed USDC is not split again
+    client.distribute_token(&usdc);
+    assert_eq!(client.claimable_token(&bob, &usdc), 50);
+
+    assert_eq!(token::Client::new(&env, &usdc).balance(&alice), 50);
+}
+
+#[test]
+fn test_remainder_to_first_recipient() {
+    let env = setup_test_env();
+    let keeper = Address::generate(&env);
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+
+    // Alice has no shares, so Bob is the first recipient eligible for the leftover
+    init_with_policy(
+        &client,
+        &token,
+        vec![&env, alice.clone(), bob.clone(), charlie.clone()],
+        vec![&env, 0, 1, 1],
+        RemainderPolicy::FirstRecipient,
+    );
+    assert_eq!(
+        client.get_config().remainder_policy,
+        RemainderPolicy::FirstRecipient
+    );
+
+    mint_tokens(&env, &contract_id, &token, 101);
+    client.distribute(&keeper);
+
+    let sac = token::Client::new(&env, &token);
+    assert_eq!(sac.balance(&alice), 0);
+    assert_eq!(sac.balance(&bob), 51);
+    assert_eq!(sac.balance(&charlie), 50);
+    assert_eq!(sac.balance(&contract_id), 0);
+    assert_eq!(client.dust(&token), 0);
+}
+
+#[test]
+fn test_remainder_to_largest_shareholder() {
+    let env = setup_test_env();
+    let keeper = Address::generate(&env);
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+
+    init_with_policy(
+        &client,
+        &token,
+        vec![&env, alice.clone(), bob.clone(), charlie.clone()],
+        vec![&env, 1, 3, 1],
+        RemainderPolicy::LargestShareholder,
+    );
+
+    mint_tokens(&env, &contract_id, &token, 102);
+    client.distribute(&keeper);
+
+    // The summary event reports the full balance as distributed
+    let ledger = env.ledger().sequence();
+    let events = splitter_events(&env, &contract_id);
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("distrib"), token.clone()).into_val(&env),
+                (102i128, 0i128, ledger).into_val(&env),
+            ),
+        ]
+    );
+
+    // 102 * 3/5 = 61, 102 * 1/5 = 20 each, leftover 1 goes to Bob
+    let sac = token::Client::new(&env, &token);
+    assert_eq!(sac.balance(&alice), 20);
+    assert_eq!(sac.balance(&bob), 62);
+    assert_eq!(sac.balance(&charlie), 20);
+    assert_eq!(sac.balance(&contract_id), 0);
+}
+
+#[test]
+fn test_remainder_round_robin() {
+    let env = setup_test_env();
+    let keeper = Address::generate(&env);
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+
+    // Bob has no shares and is skipped by the rotation
+    init_with_policy(
+        &client,
+        &token,
+        vec![&env, alice.clone(), bob.clone(), charlie.clone()],
+        vec![&env, 1, 0, 1],
+        RemainderPolicy::RoundRobin,
+    );
+
+    let sac = token::Client::new(&env, &token);
+
+    mint_tokens(&env, &contract_id, &token, 3);
+    client.distribute(&keeper);
+    assert_eq!(sac.balance(&alice), 2);
+    assert_eq!(sac.balance(&charlie), 1);
+
+    mint_tokens(&env, &contract_id, &token, 3);
+    client.distribute(&keeper);
+    assert_eq!(sac.balance(&alice), 3);
+    assert_eq!(sac.balance(&charlie), 3);
+
+    // Evenly divisible amounts do not advance the rotation
+    mint_tokens(&env, &contract_id, &token, 4);
+    client.distribute(&keeper);
+    assert_eq!(sac.balance(&alice), 5);
+    assert_eq!(sac.balance(&charlie), 5);
+
+    mint_tokens(&env, &contract_id, &token, 3);
+    client.distribute(&keeper);
+    assert_eq!(sac.balance(&alice), 7);
+    assert_eq!(sac.balance(&charlie), 6);
+
+    assert_eq!(sac.balance(&bob), 0);
+    assert_eq!(sac.balance(&contract_id), 0);
+    assert_eq!(client.dust(&token), 0);
+}
+
+#[test]
+fn test_keep_policy_dust_tracks_last_distribution() {
+    let env = setup_test_env();
+    let keeper = Address::generate(&env);
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+
+    client.init(
+        &token,
+        &vec![&env, alice.clone(), bob.clone(), charlie.clone()],
+        &vec![&env, 1, 1, 1],
+    );
+    assert_eq!(client.get_config().remainder_policy, RemainderPolicy::Keep);
+    assert_eq!(client.dust(&token), 0);
+
+    mint_tokens(&env, &contract_id, &token, 10);
+    client.distribute(&keeper);
+    assert_eq!(client.dust(&token), 1);
+
+    // The leftover rolls into the next distribution: 1 + 4 = 5, leaving 2
+    mint_tokens(&env, &contract_id, &token, 4);
+    client.distribute(&keeper);
+    assert_eq!(client.dust(&token), 2);
+
+    let sac = token::Client::new(&env, &token);
+    assert_eq!(sac.balance(&contract_id), 2);
+}
+
+#[test]
+fn test_preview_matches_distribution() {
+    let env = setup_test_env();
+    let keeper = Address::generate(&env);
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+
+    init_with_policy(
+        &client,
+        &token,
+        vec![&env, alice.clone(), bob.clone(), charlie.clone()],
+        vec![&env, 2, 0, 1],
+        RemainderPolicy::FirstRecipient,
+    );
+
+    mint_tokens(&env, &contract_id, &token, 100);
+
+    let preview = client.preview_distribution();
+    assert_eq!(
+        preview,
+        vec![
+            &env,
+            (alice.clone(), 67i128),
+            (bob.clone(), 0i128),
+            (charlie.clone(), 33i128),
+        ]
+    );
+
+    // Previewing is read-only
+    let sac = token::Client::new(&env, &token);
+    assert_eq!(sac.balance(&contract_id), 100);
+
+    client.distribute(&keeper);
+    for (recipient, amount) in preview.iter() {
+        assert_eq!(sac.balance(&recipient), amount);
+    }
+
+    // Nothing left to preview after distributing
+    assert_eq!(
+        client.preview_distribution(),
+        vec![
+            &env,
+            (alice.clone(), 0i128),
+            (bob.clone(), 0i128),
+            (charlie.clone(), 0i128),
+        ]
+    );
+
+    // With a keeper fee the preview splits what is left after the fee
+    let (contract_id, client) = create_contract(&env);
+    let dave = Address::generate(&env);
+    let erin = Address::generate(&env);
+    client.init_with_options(
+        &token,
+        &vec![&env, dave.clone(), erin.clone()],
+        &vec![&env, 2, 1],
+        &SplitterOptions {
+            remainder_policy: RemainderPolicy::FirstRecipient,
+            keeper_fee: KeeperFee::BasisPoints(100),
+            ..Default::default()
+        },
+    );
+    mint_tokens(&env, &contract_id, &token, 1000);
+
+    let preview = client.preview_distribution();
+    assert_eq!(
+        preview,
+        vec![&env, (dave.clone(), 660i128), (erin.clone(), 330i128)]
+    );
+    client.distribute(&keeper);
+    for (recipient, amount) in preview.iter() {
+        assert_eq!(sac.balance(&recipient), amount);
+    }
+    assert_eq!(sac.balance(&keeper), 10);
+}
+
+#[test]
+fn test_preview_for_amount() {
+    let env = setup_test_env();
+    let (_contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    client.init(
+        &token,
+        &vec![&env, alice.clone(), bob.clone()],
+        &vec![&env, 1, 2],
+    );
+
+    // Works regardless of the contract's balance
+    assert_eq!(
+        client.preview_for_amount(&1_000),
+        vec![&env, (alice.clone(), 333i128), (bob.clone(), 666i128)]
+    );
+    assert_eq!(
+        client.try_preview_for_amount(&-1),
+        Err(Ok(Error::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_preview_excludes_unclaimed_funds() {
+    let env = setup_test_env();
+    let keeper = Address::generate(&env);
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    init_claim_mode(
+        &client,
+        &token,
+        vec![&env, alice.clone(), bob.clone()],
+        vec![&env, 1, 1],
+    );
+
+    mint_tokens(&env, &contract_id, &token, 100);
+    client.distribute(&keeper);
+    mint_tokens(&env, &contract_id, &token, 10);
+
+    assert_eq!(
+        client.preview_distribution(),
+        vec![&env, (alice.clone(), 5i128), (bob.clone(), 5i128)]
+    );
+}
+
+#[test]
+fn test_preview_overflow_matches_distribute() {
+    let env = setup_test_env();
+    let (_contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    client.init(
+        &token,
+        &vec![&env, alice.clone(), bob.clone()],
+        &vec![&env, u32::MAX, u32::MAX],
+    );
+
+    assert_eq!(
+        client.try_preview_for_amount(&i128::MAX),
+        Err(Ok(Error::Overflow))
+    );
+}
+
+#[test]
+fn test_distribute_amount_keeps_reserve() {
+    let env = setup_test_env();
+    let keeper = Address::generate(&env);
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    client.init(
+        &token,
+        &vec![&env, alice.clone(), bob.clone()],
+        &vec![&env, 1, 2],
+    );
+
+    mint_tokens(&env, &contract_id, &token, 100);
+    client.distribute_amount(&61);
+
+    let ledger = env.ledger().sequence();
+    let events = splitter_events(&env, &contract_id);
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("distrib"), token.clone()).into_val(&env),
+                (60i128, 1i128, ledger).into_val(&env),
+            ),
+        ]
+    );
+
+    let sac = token::Client::new(&env, &token);
+    assert_eq!(sac.balance(&alice), 20);
+    assert_eq!(sac.balance(&bob), 40);
+    assert_eq!(sac.balance(&contract_id), 40);
+
+    // The reserve can be distributed later
+    client.distribute(&keeper);
+    assert_eq!(sac.balance(&alice), 33);
+    assert_eq!(sac.balance(&bob), 66);
+    assert_eq!(sac.balance(&contract_id), 1);
+}
+
+#[test]
+fn test_distribute_amount_bounded_by_balance() {
+    let env = setup_test_env();
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+
+    let alice = Address::generate(&env);
+
+    client.init(&token, &vec![&env, alice.clone()], &vec![&env, 1]);
+    mint_tokens(&env, &contract_id, &token, 50);
+
+    assert_eq!(
+        client.try_distribute_amount(&51),
+        Err(Ok(Error::InsufficientBalance))
+    );
+    assert_eq!(
+        client.try_distribute_amount(&-1),
+        Err(Ok(Error::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_distribute_amount(&0),
+        Err(Ok(Error::InvalidAmount))
+    );
+
+    // The whole balance is a valid amount
+    client.distribute_amount(&50);
+    assert_eq!(token::Client::new(&env, &token).balance(&alice), 50);
+}
+
+#[test]
+fn test_distribute_amount_excludes_unclaimed_funds() {
+    let env = setup_test_env();
+    let keeper = Address::generate(&env);
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    init_claim_mode(
+        &client,
+        &token,
+        vec![&env, alice.clone(), bob.clone()],
+        vec![&env, 1, 1],
+    );
+
+    mint_tokens(&env, &contract_id, &token, 100);
+    client.distribute(&keeper);
+    mint_tokens(&env, &contract_id, &token, 10);
+
+    // Only the 10 new tokens are available; the 100 credited are owed
+    assert_eq!(
+        client.try_distribute_amount(&12),
+        Err(Ok(Error::InsufficientBalance))
+    );
+
+    client.distribute_amount(&10);
+    assert_eq!(client.claimable(&alice), 55);
+    assert_eq!(client.claimable(&bob), 55);
+}
+
+#[test]
+fn test_version() {
+    let env = setup_test_env();
+    let (_contract_id, client) = create_contract(&env);
+
+    // Bump together with the crate version on every release
+    assert_eq!(client.version(), String::from_str(&env, "0.1.0"));
+}
+
+#[test]
+fn test_upgrade_immutable_splitter_fails() {
+    let env = setup_test_env();
+    let (_contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+    let alice = Address::generate(&env);
+
+    let new_wasm_hash = BytesN::from_array(&env, &[1; 32]);
+    assert_eq!(
+        client.try_upgrade(&new_wasm_hash),
+        Err(Ok(Error::NotInitialized))
+    );
+
+    client.init(&token, &vec![&env, alice.clone()], &vec![&env, 1]);
+    assert_eq!(
+        client.try_upgrade(&new_wasm_hash),
+        Err(Ok(Error::Immutable))
+    );
+}
+
+#[test]
+fn test_upgrade_requires_owner_auth() {
+    let env = setup_test_env();
+    let (_contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+    let owner = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    init_with_owner(
+        &client,
+        &token,
+        &owner,
+        vec![&env, alice.clone()],
+        vec![&env, 1],
+    );
+
+    env.set_auths(&[]);
+    assert!(client
+        .try_upgrade(&BytesN::from_array(&env, &[1; 32]))
+        .is_err());
+}
+
+#[test]
+fn test_migrate() {
+    let env = setup_test_env();
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+    let alice = Address::generate(&env);
+
+    assert_eq!(client.try_migrate(), Err(Ok(Error::NotInitialized)));
+
+    client.init(&token, &vec![&env, alice.clone()], &vec![&env, 1]);
+    assert_eq!(client.storage_version(), STORAGE_VERSION);
+    assert_eq!(client.migrate(), STORAGE_VERSION);
+
+    // Splitters created before storage versioning have no schema entry
+    env.as_contract(&contract_id, || {
+        env.storage().instance().remove(&symbol_short!("schema"));
+    });
+    assert_eq!(client.storage_version(), 0);
+    assert_eq!(client.migrate(), STORAGE_VERSION);
+    assert_eq!(client.storage_version(), STORAGE_VERSION);
+    assert_eq!(client.get_config().recipients, vec![&env, alice]);
+}
+
+#[test]
+fn test_owner_pauses_distributions() {
+    let env = setup_test_env();
+    let keeper = Address::generate(&env);
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+    let sac = token::Client::new(&env, &token);
+
+    let owner = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    init_with_owner(
+        &client,
+        &token,
+        &owner,
+        vec![&env, alice.clone(), bob.clone()],
+        vec![&env, 1, 1],
+    );
+    mint_tokens(&env, &contract_id, &token, 1000);
+    assert!(!client.get_config().paused);
+
+    client.pause(&owner);
+    assert_eq!(env.auths()[0].0, owner);
+    assert_eq!(
+        splitter_events(&env, &contract_id),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("paused"),).into_val(&env),
+                owner.into_val(&env),
+            ),
+        ]
+    );
+    assert!(client.is_paused());
+    assert!(client.get_config().paused);
+
+    assert_eq!(client.try_distribute(&keeper), Err(Ok(Error::Paused)));
+    assert_eq!(client.try_distribute_amount(&100), Err(Ok(Error::Paused)));
+    assert_eq!(client.try_distribute_token(&token), Err(Ok(Error::Paused)));
+    assert_eq!(sac.balance(&contract_id), 1000);
+
+    // Views keep working while paused
+    assert_eq!(client.preview_distribution().len(), 2);
+
+    client.unpause(&owner);
+    assert_eq!(
+        splitter_events(&env, &contract_id),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("unpaused"),).into_val(&env),
+                owner.into_val(&env),
+            ),
+        ]
+    );
+    assert!(!client.get_config().paused);
+
+    client.distribute(&keeper);
+    assert_eq!(sac.balance(&alice), 500);
+    assert_eq!(sac.balance(&bob), 500);
+}
+
+#[test]
+fn test_guardian_pauses_claims() {
+    let env = setup_test_env();
+    let keeper = Address::generate(&env);
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+    let sac = token::Client::new(&env, &token);
+
+    let guardian = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    // A guardian can halt payouts even on an immutable splitter
+    client.init_with_options(
+        &token,
+        &vec![&env, alice.clone()],
+        &vec![&env, 1],
+        &SplitterOptions {
+            guardian: Some(guardian.clone()),
+            payout_mode: PayoutMode::Claim,
+            ..Default::default()
+        },
+    );
+    assert!(client.is_immutable());
+    assert_eq!(client.get_config().guardian, Some(guardian.clone()));
+
+    mint_tokens(&env, &contract_id, &token, 1000);
+    client.distribute(&keeper);
+
+    client.pause(&guardian);
+    assert_eq!(env.auths()[0].0, guardian);
+    assert_eq!(client.try_claim(&alice), Err(Ok(Error::Paused)));
+    assert_eq!(
+        client.try_claim_token(&alice, &token),
+        Err(Ok(Error::Paused))
+    );
+    assert_eq!(client.claimable(&alice), 1000);
+
+    client.unpause(&guardian);
+    assert_eq!(client.claim(&alice), 1000);
+    assert_eq!(sac.balance(&alice), 1000);
+}
+
+#[test]
+fn test_pause_unauthorized() {
+    let env = setup_test_env();
+    let (_contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+    let alice = Address::generate(&env);
+
+    assert_eq!(client.try_pause(&alice), Err(Ok(Error::NotInitialized)));
+
+    // Without owner or guardian nobody can pause
+    client.init(&token, &vec![&env, alice.clone()], &vec![&env, 1]);
+    assert_eq!(client.try_pause(&alice), Err(Ok(Error::Unauthorized)));
+    assert_eq!(client.try_unpause(&alice), Err(Ok(Error::Unauthorized)));
+    assert!(!client.is_paused());
+}
+
+#[test]
+fn test_pause_requires_caller_auth() {
+    let env = setup_test_env();
+    let (_contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+    let owner = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    init_with_owner(
+        &client,
+        &token,
+        &owner,
+        vec![&env, alice.clone()],
+        vec![&env, 1],
+    );
+
+    env.set_auths(&[]);
+    assert!(client.try_pause(&owner).is_err());
+    assert!(!client.is_paused());
+}
+
+#[test]
+fn test_duplicate_recipients_rejected() {
+    let env = setup_test_env();
+    let (_contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+
+    let owner = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    assert_eq!(
+        client.try_init(
+            &token,
+            &vec![&env, alice.clone(), bob.clone(), alice.clone()],
+            &vec![&env, 1, 1, 1]
+        ),
+        Err(Ok(Error::DuplicateRecipient))
+    );
+
+    // Updates are validated the same way
+    init_with_owner(
+        &client,
+        &token,
+        &owner,
+        vec![&env, alice.clone()],
+        vec![&env, 1],
+    );
+    assert_eq!(
+        client.try_update_recipients(&vec![&env, bob.clone(), bob.clone()], &vec![&env, 1, 2]),
+        Err(Ok(Error::DuplicateRecipient))
+    );
+}
+
+#[test]
+fn test_self_recipient_rejected() {
+    let env = setup_test_env();
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+
+    let owner = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    assert_eq!(
+        client.try_init(
+            &token,
+            &vec![&env, alice.clone(), contract_id.clone()],
+            &vec![&env, 1, 1]
+        ),
+        Err(Ok(Error::SelfRecipient))
+    );
+
+    init_with_owner(
+        &client,
+        &token,
+        &owner,
+        vec![&env, alice.clone()],
+        vec![&env, 1],
+    );
+    assert_eq!(
+        client.try_update_recipients(&vec![&env, contract_id.clone()], &vec![&env, 1]),
+        Err(Ok(Error::SelfRecipient))
+    );
+}
+
+#[test]
+fn test_recipient_limit() {
+    let env = setup_test_env();
+    let (_contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+
+    let mut recipients = Vec::new(&env);
+    let mut shares = Vec::new(&env);
+    for _ in 0..=MAX_RECIPIENTS {
+        recipients.push_back(Address::generate(&env));
+        shares.push_back(1);
+    }
+
+    assert_eq!(
+        client.try_init(&token, &recipients, &shares),
+        Err(Ok(Error::TooManyRecipients))
+    );
+
+    // Exactly at the cap is fine
+    recipients.pop_back();
+    shares.pop_back();
+    client.init(&token, &recipients, &shares);
+    assert_eq!(client.get_config().max_recipients, MAX_RECIPIENTS);
+}
+
+#[test]
+fn test_configured_recipient_limit() {
+    let env = setup_test_env();
+    let (_contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+
+    let owner = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+
+    client.init_with_options(
+        &token,
+        &vec![&env, alice.clone(), bob.clone()],
+        &vec![&env, 1, 1],
+        &SplitterOptions {
+            owner: Some(owner.clone()),
+            max_recipients: Some(2),
+            ..Default::default()
+        },
+    );
+    assert_eq!(client.get_config().max_recipients, 2);
+
+    assert_eq!(
+        client.try_update_recipients(
+            &vec![&env, alice.clone(), bob.clone(), carol.clone()],
+            &vec![&env, 1, 1, 1]
+        ),
+        Err(Ok(Error::TooManyRecipients))
+    );
+    client.update_recipients(&vec![&env, alice.clone(), carol.clone()], &vec![&env, 1, 1]);
+
+    // A configured limit above the hard cap is clamped to it
+    let (_contract_id, capped) = create_contract(&env);
+    capped.init_with_options(
+        &token,
+        &vec![&env, alice.clone()],
+        &vec![&env, 1],
+        &SplitterOptions {
+            max_recipients: Some(u32::MAX),
+            ..Default::default()
+        },
+    );
+    assert_eq!(capped.get_config().max_recipients, MAX_RECIPIENTS);
+}
+
+#[test]
+fn test_distribute_to_max_recipients_fits_budget() {
+    let env = setup_test_env();
+    let keeper = Address::generate(&env);
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+    let sac = token::Client::new(&env, &token);
+
+    let mut recipients = Vec::new(&env);
+    let mut shares = Vec::new(&env);
+    for i in 0..MAX_RECIPIENTS {
+        recipients.push_back(Address::generate(&env));
+        shares.push_back(i + 1);
+    }
+    client.init(&token, &recipients, &shares);
+    mint_tokens(&env, &contract_id, &token, 1_000_000_000);
+
+    client.distribute(&keeper);
+
+    // Stay within Soroban's per-transaction limits
+    let resources = env.cost_estimate().resources();
+    assert!(resources.write_entries <= 25, "{resources:?}");
+    assert!(resources.instructions <= 100_000_000, "{resources:?}");
+    assert!(resources.mem_bytes <= 40 * 1024 * 1024, "{resources:?}");
+
+    for recipient in recipients.iter() {
+        assert!(sac.balance(&recipient) > 0);
+    }
+}
+
+#[test]
+fn test_basis_points_mode() {
+    let env = setup_test_env();
+    let keeper = Address::generate(&env);
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+    let sac = token::Client::new(&env, &token);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    client.init_with_options(
+        &token,
+        &vec![&env, alice.clone(), bob.clone()],
+        &vec![&env, 3_000, 7_000],
+        &SplitterOptions {
+            share_mode: ShareMode::BasisPoints,
+            ..Default::default()
+        },
+    );
+
+    let config = client.get_config();
+    assert_eq!(config.share_mode, ShareMode::BasisPoints);
+    assert_eq!(config.percentages_bps, vec![&env, 3_000, 7_000]);
+
+    mint_tokens(&env, &contract_id, &token, 1000);
+    client.distribute(&keeper);
+    assert_eq!(sac.balance(&alice), 300);
+    assert_eq!(sac.balance(&bob), 700);
+}
+
+#[test]
+fn test_basis_points_must_total_100_percent() {
+    let env = setup_test_env();
+    let (_contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+
+    let owner = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let options = SplitterOptions {
+        owner: Some(owner.clone()),
+        share_mode: ShareMode::BasisPoints,
+        ..Default::default()
+    };
+
+    for shares in [
+        vec![&env, 3_000, 6_999],
+        vec![&env, 3_000, 7_001],
+        vec![&env, 3, 7],
+    ] {
+        assert_eq!(
+            client.try_init_with_options(
+                &token,
+                &vec![&env, alice.clone(), bob.clone()],
+                &shares,
+                &options
+            ),
+            Err(Ok(Error::InvalidBasisPoints))
+        );
+    }
+
+    client.init_with_options(
+        &token,
+        &vec![&env, alice.clone(), bob.clone()],
+        &vec![&env, 5_000, 5_000],
+        &options,
+    );
+
+    // Updates keep the mode and its 100% requirement
+    assert_eq!(
+        client.try_update_recipients(&vec![&env, alice.clone()], &vec![&env, 1]),
+        Err(Ok(Error::InvalidBasisPoints))
+    );
+    client.update_recipients(&vec![&env, alice.clone()], &vec![&env, 10_000]);
+    assert_eq!(client.get_config().percentages_bps, vec![&env, 10_000]);
+}
+
+#[test]
+fn test_ratio_mode_reports_percentages() {
+    let env = setup_test_env();
+    let (_contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+
+    client.init(
+        &token,
+        &vec![&env, alice.clone(), bob.clone(), carol.clone()],
+        &vec![&env, 1, 2, 0],
+    );
+
+    let config = client.get_config();
+    assert_eq!(config.share_mode, ShareMode::Ratio);
+    // Rounded down, so the total can fall short of 10,000
+    assert_eq!(config.percentages_bps, vec![&env, 3_333, 6_666, 0]);
+}
+
+fn init_with_layout(
+    env: &Env,
+    client: &SimpleSplitterClient,
+    token: &Address,
+    count: u32,
+    layout: StorageLayout,
+    payout_mode: PayoutMode,
+) -> Vec<Address> {
+    let mut recipients = Vec::new(env);
+    let mut shares = Vec::new(env);
+    for i in 0..count {
+        recipients.push_back(Address::generate(env));
+        shares.push_back(i + 1);
+    }
+    client.init_with_options(
+        token,
+        &recipients,
+        &shares,
+        &SplitterOptions {
+            storage_layout: layout,
+            payout_mode,
+            ..Default::default()
+        },
+    );
+    recipients
+}
+
+#[test]
+fn test_storage_layout_benchmark() {
+    let env = setup_test_env();
+    let keeper = Address::generate(&env);
+    let token = create_token(&env);
+
+    // (view read bytes, view instructions, distribute instructions, distribute memory)
+    let mut results = std::vec::Vec::new();
+    for layout in [StorageLayout::Instance, StorageLayout::Persistent] {
+        let (contract_id, client) = create_contract(&env);
+        init_with_layout(
+            &env,
+            &client,
+            &token,
+            MAX_RECIPIENTS,
+            layout,
+            PayoutMode::Push,
+        );
+        mint_tokens(&env, &contract_id, &token, 1_000_000);
+
+        client.is_paused();
+        let view = env.cost_estimate().resources();
+
+        client.distribute(&keeper);
+        let distribution = env.cost_estimate().resources();
+
+        results.push((
+            view.read_bytes,
+            view.instructions,
+            distribution.instructions,
+            distribution.mem_bytes,
+        ));
+    }
+
+    let (instance, persistent) = (results[0], results[1]);
+    // Invocations that don't need the split no longer load it...
+    assert!(persistent.0 < instance.0);
+    assert!(persistent.1 < instance.1);
+    // ...while a distribution pays for reading the split from its own entry
+    assert!(persistent.2 > instance.2);
+    assert!(persistent.3 > instance.3);
+}
+
+#[test]
+fn test_persistent_layout_large_split() {
+    let env = setup_test_env();
+    let token = create_token(&env);
+
+    let mut recipients = Vec::new(&env);
+    let mut shares = Vec::new(&env);
+    for _ in 0..MAX_PERSISTENT_RECIPIENTS {
+        recipients.push_back(Address::generate(&env));
+        shares.push_back(1);
+    }
+
+    // The instance layout caps recipients to fit one payout transaction
+    let (_contract_id, instance) = create_contract(&env);
+    assert_eq!(
+        instance.try_init(&token, &recipients, &shares),
+        Err(Ok(Error::TooManyRecipients))
+    );
+
+    // At the cap, init and the views that load the whole split each fit in
+    // one transaction: 25 write entries, 40 read entries, 100M instructions
+    let (contract_id, client) = create_contract(&env);
+    client.init_with_options(
+        &token,
+        &recipients,
+        &shares,
+        &SplitterOptions {
+            storage_layout: StorageLayout::Persistent,
+            ..Default::default()
+        },
+    );
+    let resources = env.cost_estimate().resources();
+    assert!(resources.write_entries <= 25, "{resources:?}");
+    assert!(resources.instructions <= 100_000_000, "{resources:?}");
+
+    let config = client.get_config();
+    let resources = env.cost_estimate().resources();
+    assert!(resources.read_entries <= 40, "{resources:?}");
+    assert!(resources.instructions <= 100_000_000, "{resources:?}");
+    assert_eq!(config.storage_layout, StorageLayout::Persistent);
+    assert_eq!(config.max_recipients, MAX_PERSISTENT_RECIPIENTS);
+    assert_eq!(config.recipients, recipients);
+
+    let preview = client.preview_for_amount(&(MAX_PERSISTENT_RECIPIENTS as i128));
+    let resources = env.cost_estimate().resources();
+    assert!(resources.read_entries <= 40, "{resources:?}");
+    for (_, amount) in preview.iter() {
+        assert_eq!(amount, 1);
+    }
+
+    // Instance storage stays small no matter how many recipients there are
+    client.is_paused();
+    assert!(env.cost_estimate().resources().read_bytes < 1024);
+
+    // Every page has its TTL extended when touched
+    env.as_contract(&contract_id, || {
+        for page in 0..MAX_PERSISTENT_RECIPIENTS / 50 {
+            assert_eq!(
+                env.storage()
+                    .persistent()
+                    .get_ttl(&DataKey::RecipientPage(page)),
+                ONE_YEAR_LEDGERS
+            );
+        }
+    });
+
+    recipients.push_back(Address::generate(&env));
+    shares.push_back(1);
+    let (_contract_id, capped) = create_contract(&env);
+    assert_eq!(
+        capped.try_init_with_options(
+            &token,
+            &recipients,
+            &shares,
+            &SplitterOptions {
+                storage_layout: StorageLayout::Persistent,
+                ..Default::default()
+            },
+        ),
+        Err(Ok(Error::TooManyRecipients))
+    );
+}
+
+#[test]
+fn test_persistent_layout_update_and_distribute() {
+    let env = setup_test_env();
+    let keeper = Address::generate(&env);
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+    let sac = token::Client::new(&env, &token);
+
+    let owner = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+
+    client.init_with_options(
+        &token,
+        &vec![&env, alice.clone(), bob.clone(), carol.clone()],
+        &vec![&env, 1, 1, 2],
+        &SplitterOptions {
+            owner: Some(owner.clone()),
+            storage_layout: StorageLayout::Persistent,
+            ..Default::default()
+        },
+    );
+
+    mint_tokens(&env, &contract_id, &token, 400);
+    client.distribute(&keeper);
+    assert_eq!(sac.balance(&alice), 100);
+    assert_eq!(sac.balance(&bob), 100);
+    assert_eq!(sac.balance(&carol), 200);
+
+    // Growing past one page adds a page; shrinking back removes it
+    let mut many = Vec::new(&env);
+    let mut many_shares = Vec::new(&env);
+    for _ in 0..60 {
+        many.push_back(Address::generate(&env));
+        many_shares.push_back(1);
+    }
+    client.update_recipients(&many, &many_shares);
+    assert_eq!(client.get_config().recipients, many);
+    client.update_recipients(&vec![&env, carol.clone(), alice.clone()], &vec![&env, 3, 1]);
+    env.as_contract(&contract_id, || {
+        assert!(env.storage().persistent().has(&DataKey::RecipientPage(0)));
+        assert!(!env.storage().persistent().has(&DataKey::RecipientPage(1)));
+    });
+    assert_eq!(
+        client.get_config().recipients,
+        vec![&env, carol.clone(), alice.clone()]
+    );
+
+    mint_tokens(&env, &contract_id, &token, 400);
+    client.distribute(&keeper);
+    assert_eq!(sac.balance(&carol), 500);
+    assert_eq!(sac.balance(&alice), 200);
+    assert_eq!(sac.balance(&bob), 100);
+}
+
+fn init_five_recipients(
+    env: &Env,
+    client: &SimpleSplitterClient,
+    token: &Address,
+    policy: RemainderPolicy,
+) -> Vec<Address> {
+    let mut recipients = Vec::new(env);
+    for _ in 0..5 {
+        recipients.push_back(Address::generate(env));
+    }
+    init_with_policy(
+        client,
+        token,
+        recipients.clone(),
+        vec![env, 1, 2, 3, 4, 5],
+        policy,
+    );
+    recipients
+}
+
+#[test]
+fn test_distribute_batch_round() {
+    let env = setup_test_env();
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+    let sac = token::Client::new(&env, &token);
+
+    let recipients = init_five_recipients(&env, &client, &token, RemainderPolicy::FirstRecipient);
+    mint_tokens(&env, &contract_id, &token, 1001);
+
+    assert_eq!(
+        client.distribution_progress(),
+        DistributionProgress {
+            in_progress: false,
+            amount: 0,
+            distributed: 0,
+            next_index: 0,
+            recipient_count: 5,
+        }
+    );
+
+    // 1001 / 15 shares: 66, 133, 200, 266, 333 with a leftover of 3
+    let progress = client.distribute_batch(&2);
+    assert_eq!(
+        progress,
+        DistributionProgress {
+            in_progress: true,
+            amount: 1001,
+            distributed: 199,
+            next_index: 2,
+            recipient_count: 5,
+        }
+    );
+    assert_eq!(client.distribution_progress(), progress);
+    assert_eq!(sac.balance(&recipients.get_unchecked(0)), 66);
+    assert_eq!(sac.balance(&recipients.get_unchecked(1)), 133);
+    assert_eq!(sac.balance(&recipients.get_unchecked(2)), 0);
+
+    client.distribute_batch(&2);
+    assert_eq!(client.distribution_progress().next_index, 4);
+
+    // The last batch applies the remainder policy and closes the round
+    let progress = client.distribute_batch(&2);
+    let events = splitter_events(&env, &contract_id);
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("distrib"), token.clone()).into_val(&env),
+                (1001i128, 0i128, env.ledger().sequence()).into_val(&env),
+            ),
+        ]
+    );
+    assert_eq!(
+        progress,
+        DistributionProgress {
+            in_progress: false,
+            amount: 1001,
+            distributed: 1001,
+            next_index: 5,
+            recipient_count: 5,
+        }
+    );
+    assert!(!client.distribution_progress().in_progress);
+
+    assert_eq!(sac.balance(&recipients.get_unchecked(0)), 69);
+    assert_eq!(sac.balance(&recipients.get_unchecked(2)), 200);
+    assert_eq!(sac.balance(&recipients.get_unchecked(3)), 266);
+    assert_eq!(sac.balance(&recipients.get_unchecked(4)), 333);
+    assert_eq!(sac.balance(&contract_id), 0);
+}
+
+#[test]
+fn test_distribute_batch_matches_distribute() {
+    let env = setup_test_env();
+    let keeper = Address::generate(&env);
+    let token = create_token(&env);
+    let sac = token::Client::new(&env, &token);
+
+    for policy in [
+        RemainderPolicy::Keep,
+        RemainderPolicy::FirstRecipient,
+        RemainderPolicy::LargestShareholder,
+        RemainderPolicy::RoundRobin,
+    ] {
+        let (single_id, single) = create_contract(&env);
+        let (batched_id, batched) = create_contract(&env);
+        let single_recipients = init_five_recipients(&env, &single, &token, policy);
+        let batched_recipients = init_five_recipients(&env, &batched, &token, policy);
+
+        // Several rounds so round-robin moves its cursor
+        for amount in [1001, 7, 29] {
+            mint_tokens(&env, &single_id, &token, amount);
+            mint_tokens(&env, &batched_id, &token, amount);
+
+            single.distribute(&keeper);
+            while batched.distribute_batch(&3).in_progress {}
+
+            assert_eq!(single.dust(&token), batched.dust(&token));
+            for i in 0..5 {
+                assert_eq!(
+                    sac.balance(&single_recipients.get_unchecked(i)),
+                    sac.balance(&batched_recipients.get_unchecked(i)),
+                    "{policy:?} recipient {i}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_distribute_batch_deposit_mid_round() {
+    let env = setup_test_env();
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+    let sac = token::Client::new(&env, &token);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    client.init(
+        &token,
+        &vec![&env, alice.clone(), bob.clone()],
+        &vec![&env, 1, 1],
+    );
+
+    mint_tokens(&env, &contract_id, &token, 1000);
+    client.distribute_batch(&1);
+    assert_eq!(sac.balance(&alice), 500);
+
+    // A deposit arriving mid-round does not change this round's payouts
+    mint_tokens(&env, &contract_id, &token, 600);
+    let progress = client.distribute_batch(&1);
+    assert!(!progress.in_progress);
+    assert_eq!(progress.amount, 1000);
+    assert_eq!(sac.balance(&bob), 500);
+    assert_eq!(sac.balance(&contract_id), 600);
+
+    // The next round picks it up
+    client.distribute_batch(&10);
+    assert_eq!(sac.balance(&alice), 800);
+    assert_eq!(sac.balance(&bob), 800);
+    assert_eq!(sac.balance(&contract_id), 0);
+}
+
+#[test]
+fn test_distribute_batch_claim_mode_mid_round() {
+    let env = setup_test_env();
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+    let sac = token::Client::new(&env, &token);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    init_claim_mode(
+        &client,
+        &token,
+        vec![&env, alice.clone(), bob.clone(), carol.clone()],
+        vec![&env, 1, 1, 1],
+    );
+
+    mint_tokens(&env, &contract_id, &token, 300);
+    client.distribute_batch(&1);
+
+    // Claims and deposits in the middle of a round
+    assert_eq!(client.claim(&alice), 100);
+    mint_tokens(&env, &contract_id, &token, 30);
+    client.distribute_batch(&2);
+
+    assert_eq!(client.claimable(&bob), 100);
+    assert_eq!(client.claimable(&carol), 100);
+    assert_eq!(sac.balance(&contract_id), 230);
+
+    // Only the new deposit is split next
+    client.distribute_batch(&3);
+    assert_eq!(client.claimable(&alice), 10);
+    assert_eq!(client.claimable(&bob), 110);
+    assert_eq!(client.claimable(&carol), 110);
+    assert_eq!(sac.balance(&alice), 100);
+}
+
+#[test]
+fn test_round_in_progress_blocks_other_changes() {
+    let env = setup_test_env();
+    let keeper = Address::generate(&env);
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+
+    let owner = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    init_with_owner(
+        &client,
+        &token,
+        &owner,
+        vec![&env, alice.clone(), bob.clone()],
+        vec![&env, 1, 1],
+    );
+    mint_tokens(&env, &contract_id, &token, 1000);
+
+    assert_eq!(
+        client.try_distribute_batch(&0),
+        Err(Ok(Error::InvalidBatchSize))
+    );
+
+    client.distribute_batch(&1);
+    assert_eq!(
+        client.try_distribute(&keeper),
+        Err(Ok(Error::RoundInProgress))
+    );
+    assert_eq!(
+        client.try_distribute_amount(&10),
+        Err(Ok(Error::RoundInProgress))
+    );
+    assert_eq!(
+        client.try_update_recipients(&vec![&env, alice.clone()], &vec![&env, 1]),
+        Err(Ok(Error::RoundInProgress))
+    );
+
+    client.distribute_batch(&1);
+    client.update_recipients(&vec![&env, alice.clone()], &vec![&env, 1]);
+    assert_eq!(client.distribution_progress().recipient_count, 1);
+}
+
+#[test]
+fn test_distribute_batch_large_persistent_split() {
+    let env = setup_test_env();
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+    let sac = token::Client::new(&env, &token);
+
+    let recipients = init_with_layout(
+        &env,
+        &client,
+        &token,
+        200,
+        StorageLayout::Persistent,
+        PayoutMode::Push,
+    );
+    // Shares 1..=200 sum to 20,100
+    mint_tokens(&env, &contract_id, &token, 20_100 * 1000 + 7);
+
+    let mut batches = 0;
+    loop {
+        let progress = client.distribute_batch(&MAX_RECIPIENTS);
+        batches += 1;
+
+        // Each batch fits in a single transaction
+        let resources = env.cost_estimate().resources();
+        assert!(resources.write_entries <= 25, "{resources:?}");
+        assert!(resources.read_entries <= 40, "{resources:?}");
+
+        if !progress.in_progress {
+            break;
+        }
+    }
+    assert_eq!(batches, 10);
+
+    for (i, recipient) in recipients.iter().enumerate() {
+        assert_eq!(sac.balance(&recipient), (i as i128 + 1) * 1000);
+    }
+    // Default remainder policy keeps the leftover
+    assert_eq!(client.dust(&token), 7);
+    assert_eq!(sac.balance(&contract_id), 7);
+}
+
+#[test]
+fn test_keeper_fee_fixed() {
+    let env = setup_test_env();
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+    let sac = token::Client::new(&env, &token);
+    let keeper = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    client.init_with_options(
+        &token,
+        &vec![&env, alice.clone(), bob.clone()],
+        &vec![&env, 1, 1],
+        &SplitterOptions {
+            keeper_fee: KeeperFee::Fixed(50),
+            ..Default::default()
+        },
+    );
+
+    mint_tokens(&env, &contract_id, &token, 10_000);
+    client.distribute(&keeper);
+
+    assert_eq!(env.auths()[0].0, keeper);
+
+    // The keeper event precedes the payouts
+    assert_eq!(
+        splitter_events(&env, &contract_id).slice(0..1),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("keeper"), token.clone(), keeper.clone()).into_val(&env),
+                50i128.into_val(&env),
+            ),
+        ]
+    );
+
+    assert_eq!(sac.balance(&keeper), 50);
+    assert_eq!(sac.balance(&alice), 4975);
+    assert_eq!(sac.balance(&bob), 4975);
+}
+
+#[test]
+fn test_keeper_fee_basis_points() {
+    let env = setup_test_env();
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+    let sac = token::Client::new(&env, &token);
+    let keeper = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    client.init_with_options(
+        &token,
+        &vec![&env, alice.clone()],
+        &vec![&env, 1],
+        &SplitterOptions {
+            keeper_fee: KeeperFee::BasisPoints(25),
+            ..Default::default()
+        },
+    );
+
+    mint_tokens(&env, &contract_id, &token, 10_000);
+    client.distribute(&keeper);
+
+    // 0.25% of 10,000
+    assert_eq!(sac.balance(&keeper), 25);
+    assert_eq!(sac.balance(&alice), 9975);
+    assert_eq!(client.get_config().keeper_fee, KeeperFee::BasisPoints(25));
+}
+
+#[test]
+fn test_keeper_fee_capped() {
+    let env = setup_test_env();
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+    let sac = token::Client::new(&env, &token);
+    let keeper = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    client.init_with_options(
+        &token,
+        &vec![&env, alice.clone()],
+        &vec![&env, 1],
+        &SplitterOptions {
+            keeper_fee: KeeperFee::Fixed(500),
+            ..Default::default()
+        },
+    );
+
+    // A fixed fee above 1% of the balance is capped
+    mint_tokens(&env, &contract_id, &token, 10_000);
+    client.distribute(&keeper);
+    assert_eq!(sac.balance(&keeper), 100);
+    assert_eq!(sac.balance(&alice), 9900);
+
+    // Balances too small to earn a fee are split in full
+    mint_tokens(&env, &contract_id, &token, 50);
+    client.distribute(&keeper);
+    assert_eq!(sac.balance(&keeper), 100);
+    assert_eq!(sac.balance(&alice), 9950);
+}
+
+#[test]
+fn test_invalid_keeper_fee() {
+    let env = setup_test_env();
+    let (_contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+    let alice = Address::generate(&env);
+
+    for keeper_fee in [
+        KeeperFee::Fixed(-1),
+        KeeperFee::BasisPoints(MAX_KEEPER_FEE_BPS + 1),
+    ] {
+        assert_eq!(
+            client.try_init_with_options(
+                &token,
+                &vec![&env, alice.clone()],
+                &vec![&env, 1],
+                &SplitterOptions {
+                    keeper_fee,
+                    ..Default::default()
+                },
+            ),
+            Err(Ok(Error::InvalidKeeperFee))
+        );
+    }
+}
+
+#[test]
+fn test_distribute_requires_caller_auth() {
+    let env = setup_test_env();
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+    let keeper = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    client.init(&token, &vec![&env, alice.clone()], &vec![&env, 1]);
+    mint_tokens(&env, &contract_id, &token, 100);
+
+    env.set_auths(&[]);
+    assert!(client.try_distribute(&keeper).is_err());
+    assert_eq!(token::Client::new(&env, &token).balance(&alice), 0);
+}
+
+#[test]
+fn test_keeper_fee_claim_mode() {
+    let env = setup_test_env();
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+    let sac = token::Client::new(&env, &token);
+    let keeper = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    client.init_with_options(
+        &token,
+        &vec![&env, alice.clone(), bob.clone()],
+        &vec![&env, 1, 1],
+        &SplitterOptions {
+            keeper_fee: KeeperFee::Fixed(100),
+            payout_mode: PayoutMode::Claim,
+            ..Default::default()
+        },
+    );
+
+    mint_tokens(&env, &contract_id, &token, 10_000);
+    client.distribute(&keeper);
+
+    // The fee is paid out immediately while shares are only credited
+    assert_eq!(sac.balance(&keeper), 100);
+    assert_eq!(client.claimable(&alice), 4950);
+    assert_eq!(client.claimable(&bob), 4950);
+    assert_eq!(sac.balance(&contract_id), 9900);
+
+    // Credited funds do not earn the keeper a second fee
+    client.distribute(&keeper);
+    assert_eq!(sac.balance(&keeper), 100);
+}
+
+#[test]
+fn test_min_distribution() {
+    let env = setup_test_env();
+    let keeper = Address::generate(&env);
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+    let sac = token::Client::new(&env, &token);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    client.init_with_options(
+        &token,
+        &vec![&env, alice.clone(), bob.clone()],
+        &vec![&env, 1, 1],
+        &SplitterOptions {
+            min_distribution: 1000,
+            ..Default::default()
+        },
+    );
+    assert_eq!(client.get_config().min_distribution, 1000);
+
+    mint_tokens(&env, &contract_id, &token, 999);
+    assert_eq!(
+        client.try_distribute(&keeper),
+        Err(Ok(Error::BelowMinDistribution))
+    );
+    assert_eq!(sac.balance(&contract_id), 999);
+
+    // Reaching the threshold splits the whole balance
+    mint_tokens(&env, &contract_id, &token, 1);
+    client.distribute(&keeper);
+    assert_eq!(sac.balance(&alice), 500);
+    assert_eq!(sac.balance(&bob), 500);
+}
+
+#[test]
+fn test_min_distribution_applies_to_amount_and_batch() {
+    let env = setup_test_env();
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    client.init_with_options(
+        &token,
+        &vec![&env, alice.clone(), bob.clone()],
+        &vec![&env, 1, 1],
+        &SplitterOptions {
+            min_distribution: 1000,
+            ..Default::default()
+        },
+    );
+
+    mint_tokens(&env, &contract_id, &token, 500);
+    assert_eq!(
+        client.try_distribute_batch(&1),
+        Err(Ok(Error::BelowMinDistribution))
+    );
+    assert!(!client.distribution_progress().in_progress);
+
+    assert_eq!(
+        client.try_distribute_token(&token),
+        Err(Ok(Error::BelowMinDistribution))
+    );
+    // The threshold is in primary-token units; other tokens are not limited
+    let other = create_token(&env);
+    mint_tokens(&env, &contract_id, &other, 10);
+    client.distribute_token(&other);
+    assert_eq!(token::Client::new(&env, &other).balance(&alice), 5);
+
+    mint_tokens(&env, &contract_id, &token, 1500);
+    assert_eq!(
+        client.try_distribute_amount(&999),
+        Err(Ok(Error::BelowMinDistribution))
+    );
+    client.distribute_amount(&1000);
+    assert_eq!(token::Client::new(&env, &token).balance(&alice), 500);
+}
+
+#[test]
+fn test_negative_min_distribution_rejected() {
+    let env = setup_test_env();
+    let (_contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+    let alice = Address::generate(&env);
+
+    assert_eq!(
+        client.try_init_with_options(
+            &token,
+            &vec![&env, alice.clone()],
+            &vec![&env, 1],
+            &SplitterOptions {
+                min_distribution: -1,
+                ..Default::default()
+            },
+        ),
+        Err(Ok(Error::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_min_ledger_gap() {
+    let env = setup_test_env();
+    let keeper = Address::generate(&env);
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+    let alice = Address::generate(&env);
+
+    client.init_with_options(
+        &token,
+        &vec![&env, alice.clone()],
+        &vec![&env, 1],
+        &SplitterOptions {
+            min_ledger_gap: 10,
+            ..Default::default()
+        },
+    );
+    assert_eq!(client.get_config().min_ledger_gap, 10);
+    // Nothing distributed yet
+    assert_eq!(client.next_distribution_ledger(&token), 0);
+
+    let start = env.ledger().sequence();
+    mint_tokens(&env, &contract_id, &token, 100);
+    client.distribute(&keeper);
+    assert_eq!(client.next_distribution_ledger(&token), start + 10);
+
+    mint_tokens(&env, &contract_id, &token, 100);
+    env.ledger().set_sequence_number(start + 9);
+    assert_eq!(
+        client.try_distribute(&keeper),
+        Err(Ok(Error::DistributionTooSoon))
+    );
+    assert_eq!(
+        client.try_distribute_token(&token),
+        Err(Ok(Error::DistributionTooSoon))
+    );
+    assert_eq!(
+        client.try_distribute_amount(&50),
+        Err(Ok(Error::DistributionTooSoon))
+    );
+    assert_eq!(
+        client.try_distribute_batch(&1),
+        Err(Ok(Error::DistributionTooSoon))
+    );
+
+    env.ledger().set_sequence_number(start + 10);
+    client.distribute(&keeper);
+    assert_eq!(token::Client::new(&env, &token).balance(&alice), 200);
+    assert_eq!(client.next_distribution_ledger(&token), start + 20);
+}
+
+#[test]
+fn test_min_ledger_gap_batch_round() {
+    let env = setup_test_env();
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+
+    client.init_with_options(
+        &token,
+        &vec![&env, alice.clone(), bob.clone(), charlie.clone()],
+        &vec![&env, 1, 1, 1],
+        &SplitterOptions {
+            min_ledger_gap: 5,
+            ..Default::default()
+        },
+    );
+    mint_tokens(&env, &contract_id, &token, 300);
+
+    // Batches of an open round are not rate limited; the gap starts when it ends
+    let start = env.ledger().sequence();
+    client.distribute_batch(&1);
+    client.distribute_batch(&1);
+    assert_eq!(client.next_distribution_ledger(&token), 0);
+    client.distribute_batch(&1);
+    assert_eq!(client.next_distribution_ledger(&token), start + 5);
+    assert_eq!(token::Client::new(&env, &token).balance(&charlie), 100);
+
+    mint_tokens(&env, &contract_id, &token, 300);
+    assert_eq!(
+        client.try_distribute_batch(&1),
+        Err(Ok(Error::DistributionTooSoon))
+    );
+}
+
+#[test]
+fn test_min_ledger_gap_is_per_token() {
+    let env = setup_test_env();
+    let keeper = Address::generate(&env);
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+    let other_token = create_token(&env);
+    let alice = Address::generate(&env);
+
+    client.init_with_options(
+        &token,
+        &vec![&env, alice.clone()],
+        &vec![&env, 1],
+        &SplitterOptions {
+            min_ledger_gap: 10,
+            ..Default::default()
+        },
+    );
+
+    // Distributing another token does not delay the primary token
+    let start = env.ledger().sequence();
+    mint_tokens(&env, &contract_id, &other_token, 100);
+    client.distribute_token(&other_token);
+    assert_eq!(client.next_distribution_ledger(&other_token), start + 10);
+    assert_eq!(client.next_distribution_ledger(&token), 0);
+
+    mint_tokens(&env, &contract_id, &token, 100);
+    client.distribute(&keeper);
+    assert_eq!(token::Client::new(&env, &token).balance(&alice), 100);
+
+    mint_tokens(&env, &contract_id, &other_token, 100);
+    assert_eq!(
+        client.try_distribute_token(&other_token),
+        Err(Ok(Error::DistributionTooSoon))
+    );
+}
+
+#[test]
+fn test_min_ledger_gap_ignores_empty_distributions() {
+    let env = setup_test_env();
+    let keeper = Address::generate(&env);
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    client.init_with_options(
+        &token,
+        &vec![&env, alice.clone(), bob.clone()],
+        &vec![&env, 1, 1],
+        &SplitterOptions {
+            min_ledger_gap: 10,
+            ..Default::default()
+        },
+    );
+
+    // Nothing to split, and a single unit that rounds to zero payouts
+    client.distribute(&keeper);
+    mint_tokens(&env, &contract_id, &token, 1);
+    client.distribute_token(&token);
+    assert_eq!(client.dust(&token), 1);
+    assert_eq!(client.next_distribution_ledger(&token), 0);
+
+    let start = env.ledger().sequence();
+    mint_tokens(&env, &contract_id, &token, 99);
+    client.distribute(&keeper);
+    assert_eq!(token::Client::new(&env, &token).balance(&alice), 50);
+    assert_eq!(client.next_distribution_ledger(&token), start + 10);
+}
+
+#[test]
+fn test_cancel_round_unblocks_frozen_recipient() {
+    let env = setup_test_env();
+    let keeper = Address::generate(&env);
+    let (contract_id, client) = create_contract(&env);
+    let token_admin = Address::generate(&env);
+    let asset = env.register_stellar_asset_contract_v2(token_admin);
+    asset.issuer().set_flag(IssuerFlags::RevocableFlag);
+    let token = asset.address();
+    let sac = token::Client::new(&env, &token);
+
+    let owner = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    init_with_owner(
+        &client,
+        &token,
+        &owner,
+        vec![&env, alice.clone(), bob.clone(), carol.clone()],
+        vec![&env, 1, 1, 1],
+    );
+    mint_tokens(&env, &contract_id, &token, 300);
+
+    assert_eq!(
+        client.try_cancel_round(&owner),
+        Err(Ok(Error::NoRoundInProgress))
+    );
+
+    // Bob loses authorization after the round has started
+    client.distribute_batch(&1);
+    token::StellarAssetClient::new(&env, &token).set_authorized(&bob, &false);
+    assert!(client.try_distribute_batch(&1).is_err());
+    assert_eq!(
+        client.try_update_recipients(&vec![&env, alice.clone(), carol.clone()], &vec![&env, 1, 1]),
+        Err(Ok(Error::RoundInProgress))
+    );
+
+    let progress = client.cancel_round(&owner);
+    assert_eq!(env.auths()[0].0, owner);
+    assert!(!progress.in_progress);
+    assert_eq!(progress.next_index, 1);
+    assert_eq!(progress.distributed, 100);
+    assert!(!client.distribution_progress().in_progress);
+
+    // The owner drops Bob and the unpaid rest is split by the next distribution
+    client.update_recipients(&vec![&env, alice.clone(), carol.clone()], &vec![&env, 1, 1]);
+    client.distribute(&keeper);
+    assert_eq!(sac.balance(&alice), 200);
+    assert_eq!(sac.balance(&carol), 100);
+    assert_eq!(sac.balance(&bob), 0);
+}
+
+#[test]
+fn test_cancel_round_after_timeout() {
+    let env = setup_test_env();
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+    let other_token = create_token(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let stranger = Address::generate(&env);
+
+    client.init(
+        &token,
+        &vec![&env, alice.clone(), bob.clone()],
+        &vec![&env, 1, 1],
+    );
+    mint_tokens(&env, &contract_id, &token, 100);
+    let start = env.ledger().sequence();
+    client.distribute_batch(&1);
+
+    // An open round only blocks the primary token
+    mint_tokens(&env, &contract_id, &other_token, 10);
+    client.distribute_token(&other_token);
+    assert_eq!(token::Client::new(&env, &other_token).balance(&bob), 5);
+    assert_eq!(
+        client.try_distribute_token(&token),
+        Err(Ok(Error::RoundInProgress))
+    );
+
+    // Without an owner or guardian, anyone may cancel once the round stalls
+    env.ledger()
+        .set_sequence_number(start + ROUND_TIMEOUT_LEDGERS - 1);
+    assert_eq!(
+        client.try_cancel_round(&stranger),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert!(client.distribution_progress().in_progress);
+
+    env.ledger()
+        .set_sequence_number(start + ROUND_TIMEOUT_LEDGERS);
+    let progress = client.cancel_round(&stranger);
+    assert_eq!(env.auths()[0].0, stranger);
+    assert_eq!(progress.next_index, 1);
+    assert!(!client.distribution_progress().in_progress);
+}
+
+#[test]
+fn test_guardian_can_cancel_round() {
+    let env = setup_test_env();
+    let (contract_id, client) = create_contract(&env);
+    let token = create_token(&env);
+    let guardian = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    client.init_with_options(
+        &token,
+        &vec![&env, alice.clone(), bob.clone()],
+        &vec![&env, 1, 1],
+        &SplitterOptions {
+            guardian: Some(guardian.clone()),
+            ..Default::default()
+        },
+    );
+    mint_tokens(&env, &contract_id, &token, 100);
+    client.distribute_batch(&1);
+
+    client.cancel_round(&guardian);
+    assert_eq!(env.auths()[0].0, guardian);
+    assert!(!client.distribution_progress().in_progress);
+}
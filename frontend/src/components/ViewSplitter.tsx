@@ -2,7 +2,12 @@ import { useQuery, useMutation, useQueryClient } from '@tanstack/react-query';
 import { toast } from 'sonner';
 import { Copy } from 'lucide-react';
 import { useWallet } from '@/contexts/wallet-context';
-import { getSplitterConfig, getSplitterBalance, distributeSplitter } from '@/lib/contracts';
+import {
+  getSplitterConfig,
+  getSplitterBalance,
+  getSplitterPreview,
+  distributeSplitter,
+} from '@/lib/contracts';
 import { EXPLORER_URL } from '@/config';
 import { Button } from '@/components/ui/button';
 import { Card, CardContent, CardDescription, CardHeader, CardTitle } from '@/components/ui/card';
@@ -36,6 +41,12 @@ export function ViewSplitter({ address }: ViewSplitterProps) {
     enabled: !!address && address.length === 56 && address.startsWith('C'),
   });
 
+  const { data: preview } = useQuery({
+    queryKey: ['splitter-preview', address, balance],
+    queryFn: () => getSplitterPreview(address),
+    enabled: !!config && balance !== undefined,
+  });
+
   const distributeMutation = useMutation({
     mutationFn: async () => {
       if (!kit || !userAddress) throw new Error('Wallet not connected');
@@ -44,6 +55,7 @@ export function ViewSplitter({ address }: ViewSplitterProps) {
     onSuccess: () => {
       toast.success('Distribution initiated successfully!');
       queryClient.invalidateQueries({ queryKey: ['splitter-balance', address] });
+      queryClient.invalidateQueries({ queryKey: ['splitter-preview', address] });
     },
     onError: (error: Error) => {
       toast.error(error.message || 'Failed to distribute');
@@ -52,13 +64,15 @@ export function ViewSplitter({ address }: ViewSplitterProps) {
 
   const totalShares = config?.shares.reduce((a, b) => a + b, 0) || 1;
   const balanceNum = parseFloat(balance || '0');
+  const belowMinimum = balanceNum < parseFloat(config?.minDistribution || '0');
 
-  const calculateDistributableAmount = (share: number) => {
-    return ((balanceNum * share) / totalShares).toFixed(7);
+  // Prefer the contract's own preview; fall back to an estimate while it loads
+  const calculateDistributableAmount = (recipient: string, share: number) => {
+    return preview?.get(recipient) ?? ((balanceNum * share) / totalShares).toFixed(7);
   };
 
-  const calculatePercentage = (share: number) => {
-    return ((share / totalShares) * 100).toFixed(2);
+  const calculatePercentage = (index: number) => {
+    return ((config?.percentagesBps[index] ?? 0) / 100).toFixed(2);
   };
 
   // Invalid address format
@@ -133,12 +147,30 @@ export function ViewSplitter({ address }: ViewSplitterProps) {
 
           <Button
             onClick={() => distributeMutation.mutate()}
-            disabled={!isConnected || distributeMutation.isPending || balanceNum === 0}
+            disabled={
+              !isConnected ||
+              distributeMutation.isPending ||
+              balanceNum === 0 ||
+              belowMinimum ||
+              config.paused
+            }
             className="w-full"
           >
             {distributeMutation.isPending ? 'Distributing...' : 'Distribute'}
           </Button>
 
+          {config.paused && (
+            <p className="text-sm text-destructive text-center">
+              Distributions are paused by the splitter's owner or guardian
+            </p>
+          )}
+
+          {belowMinimum && !config.paused && (
+            <p className="text-sm text-muted-foreground text-center">
+              Balance is below this splitter's minimum distribution of {config.minDistribution} PYUSD
+            </p>
+          )}
+
           {!isConnected && (
             <p className="text-sm text-muted-foreground text-center">
               Connect your wallet to distribute funds
@@ -188,10 +220,10 @@ export function ViewSplitter({ address }: ViewSplitterProps) {
                   </TableCell>
                   <TableCell className="text-right">{config.shares[index]}</TableCell>
                   <TableCell className="text-right">
-                    {calculatePercentage(config.shares[index])}%
+                    {calculatePercentage(index)}%
                   </TableCell>
                   <TableCell className="text-right">
-                    {calculateDistributableAmount(config.shares[index])} PYUSD
+                    {calculateDistributableAmount(recipient, config.shares[index])} PYUSD
                   </TableCell>
                 </TableRow>
               ))}
@@ -17,6 +17,38 @@ export interface SplitterConfig {
   token: string;
   recipients: string[];
   shares: number[];
+  owner?: string;
+  immutable: boolean;
+  paused: boolean;
+  shareMode: 'Ratio' | 'BasisPoints';
+  // Per-recipient portion in basis points, computed by the contract
+  percentagesBps: number[];
+  // Smallest balance `distribute` will split, formatted like getSplitterBalance
+  minDistribution: string;
+}
+
+// Contract error codes returned by SimpleSplitterFactory (see contracts/simple-splitter-factory)
+const FACTORY_ERRORS: Record<number, string> = {
+  1: 'Factory is already initialized',
+  2: 'Factory is not initialized',
+  3: 'Invalid splitter configuration (check recipients and shares)',
+  4: 'This salt has already been used',
+  5: 'Unknown splitter version',
+  6: 'Recipients and shares must have the same length',
+  7: 'At least one share must be greater than zero',
+  8: 'Each recipient can only be listed once',
+  9: 'The splitter cannot be one of its own recipients',
+  10: 'Too many recipients for one splitter',
+  11: 'Basis-point shares must add up to exactly 10,000 (100%)',
+  12: 'Keeper fee must be between 0 and 100 basis points',
+  13: 'Minimum distribution cannot be negative',
+  14: 'The splitter failed to initialize',
+};
+
+// Extract the numeric code from a simulation error such as "Error(Contract, #3)"
+export function contractErrorCode(error: string): number | undefined {
+  const match = error.match(/Error\(Contract, #(\d+)\)/);
+  return match ? Number(match[1]) : undefined;
 }
 
 export async function createSplitter(
@@ -38,6 +70,7 @@ export async function createSplitter(
     .addOperation(
       factory.call(
         'create',
+        nativeToScVal(new Address(userAddress), { type: 'address' }),
         nativeToScVal(saltArray, { type: 'bytes' }),
         nativeToScVal(new Address(PYUSD_SAC_CONTRACT), { type: 'address' }),
         nativeToScVal(recipients.map(r => new Address(r)), { type: 'address' }),
@@ -51,6 +84,10 @@ export async function createSplitter(
 
   if (rpc.Api.isSimulationError(simResponse)) {
     const errorMsg = simResponse.error || 'Unknown error';
+    const code = contractErrorCode(errorMsg);
+    if (code !== undefined && FACTORY_ERRORS[code]) {
+      throw new Error(FACTORY_ERRORS[code]);
+    }
     throw new Error(`Simulation failed: ${errorMsg}`);
   }
 
@@ -108,11 +145,28 @@ export async function getSplitterConfig(splitterAddress: string): Promise<Splitt
   const response = await server.simulateTransaction(transaction);
 
   if (rpc.Api.isSimulationSuccess(response) && response.result?.retval) {
-    const [token, recipients, shares] = scValToNative(response.result.retval);
+    const {
+      token,
+      recipients,
+      shares,
+      owner,
+      immutable,
+      paused,
+      share_mode,
+      percentages_bps,
+      min_distribution,
+    } = scValToNative(response.result.retval);
     return {
       token,
       recipients,
       shares,
+      owner: owner ?? undefined,
+      immutable,
+      paused,
+      // Unit enum variants decode as a single-element array, e.g. ['Ratio']
+      shareMode: share_mode[0],
+      percentagesBps: percentages_bps,
+      minDistribution: (Number(min_distribution) / 10_000_000).toFixed(7),
     };
   }
 
@@ -152,6 +206,40 @@ export async function getSplitterBalance(splitterAddress: string): Promise<strin
   return '0';
 }
 
+// Exact per-recipient payouts for the current balance, computed by the contract
+export async function getSplitterPreview(splitterAddress: string): Promise<Map<string, string>> {
+  const contract = new Contract(splitterAddress);
+
+  // For simulations, use placeholder account
+  const placeholderSource = 'GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF';
+  const account = await server.getAccount(placeholderSource).catch(() => {
+    return new rpc.Server(SOROBAN_RPC_URL).getAccount(placeholderSource).catch(() => ({
+      accountId: () => placeholderSource,
+      sequenceNumber: () => '0',
+      incrementSequenceNumber: () => {},
+    } as Account));
+  });
+
+  const transaction = new TransactionBuilder(account, {
+    fee: BASE_FEE,
+    networkPassphrase: NETWORK_PASSPHRASE,
+  })
+    .addOperation(contract.call('preview_distribution'))
+    .setTimeout(30)
+    .build();
+
+  const response = await server.simulateTransaction(transaction);
+
+  if (rpc.Api.isSimulationSuccess(response) && response.result?.retval) {
+    const payouts: [string, bigint][] = scValToNative(response.result.retval);
+    return new Map(
+      payouts.map(([recipient, amount]) => [recipient, (Number(amount) / 10_000_000).toFixed(7)])
+    );
+  }
+
+  throw new Error('Failed to preview distribution');
+}
+
 export async function distributeSplitter(
   kit: StellarWalletsKit,
   userAddress: string,
@@ -164,7 +252,9 @@ export async function distributeSplitter(
     fee: BASE_FEE,
     networkPassphrase: NETWORK_PASSPHRASE,
   })
-    .addOperation(contract.call('distribute'))
+    .addOperation(
+      contract.call('distribute', nativeToScVal(new Address(userAddress), { type: 'address' }))
+    )
     .setTimeout(30)
     .build();
 